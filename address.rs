@@ -0,0 +1,441 @@
+use crate::entity::{merge_entities, Entity};
+use crate::parser::{state_code, CA_PROVINCES, US_STATES};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Street suffixes as written, with the USPS abbreviation they normalize to
+const STREET_SUFFIXES: &[(&str, &str)] = &[
+    ("street", "St"), ("st", "St"),
+    ("avenue", "Ave"), ("ave", "Ave"), ("av", "Ave"),
+    ("road", "Rd"), ("rd", "Rd"),
+    ("boulevard", "Blvd"), ("blvd", "Blvd"),
+    ("drive", "Dr"), ("dr", "Dr"),
+    ("lane", "Ln"), ("ln", "Ln"),
+    ("court", "Ct"), ("ct", "Ct"),
+    ("place", "Pl"), ("pl", "Pl"),
+    ("terrace", "Ter"), ("ter", "Ter"),
+    ("parkway", "Pkwy"), ("pkwy", "Pkwy"),
+    ("circle", "Cir"), ("cir", "Cir"),
+    ("highway", "Hwy"), ("hwy", "Hwy"),
+    ("square", "Sq"), ("sq", "Sq"),
+    ("trail", "Trl"), ("trl", "Trl"),
+    ("crescent", "Cres"), ("cres", "Cres"),
+    ("plaza", "Plz"), ("plz", "Plz"),
+    ("alley", "Aly"), ("aly", "Aly"),
+    ("way", "Way"), ("pike", "Pike"), ("loop", "Loop"), ("row", "Row"),
+];
+
+const DIRECTIONALS: &[(&str, &str)] = &[
+    ("north", "N"), ("south", "S"), ("east", "E"), ("west", "W"),
+    ("northeast", "NE"), ("northwest", "NW"), ("southeast", "SE"), ("southwest", "SW"),
+    ("n", "N"), ("s", "S"), ("e", "E"), ("w", "W"),
+    ("ne", "NE"), ("nw", "NW"), ("se", "SE"), ("sw", "SW"),
+];
+
+const UNIT_DESIGNATORS: &[(&str, &str)] = &[
+    ("apartment", "Apt"), ("apt", "Apt"),
+    ("unit", "Unit"),
+    ("suite", "Ste"), ("ste", "Ste"),
+];
+
+lazy_static! {
+    /// A street line, then optionally a unit, "City, ST" and a ZIP or postal code
+    static ref ADDRESS_PATTERN: Regex = {
+        let mut suffixes: Vec<&str> = STREET_SUFFIXES.iter().map(|(written, _)| *written).collect();
+        suffixes.sort_by_key(|s| std::cmp::Reverse(s.len()));
+        let regions: Vec<&str> = US_STATES
+            .iter()
+            .chain(CA_PROVINCES.iter())
+            .flat_map(|(code, name)| [*code, *name])
+            .collect();
+        Regex::new(&format!(
+            r"\b\d{{1,6}}[A-Za-z]?\s+(?:[A-Z0-9][A-Za-z0-9.'-]*\s+){{1,4}}?(?i:{suffixes})\b\.?(?:\s+(?:[NSEW]|NE|NW|SE|SW)\b\.?)?(?:,?\s+(?i:apartment|apt|unit|suite|ste)\.?\s*\#?\s*[A-Za-z0-9-]+|,?\s+\#\s*[A-Za-z0-9-]+)?(?:(?:,?\s+{word}){{1,3}}?,?\s+(?:{regions})\b(?:,?\s+(?:{postal})\b)?|,\s*{word}(?:\s+{word}){{0,2}}|\s+(?:{postal})\b)?",
+            suffixes = suffixes.join("|"),
+            regions = regions.join("|"),
+            word = r"[A-Z][A-Za-z.'-]*",
+            postal = r"\d{5}(?:-\d{4})?|[A-Z]\d[A-Z]\s?\d[A-Z]\d",
+        )).unwrap()
+    };
+    static ref ZIP_CODE: Regex = Regex::new(r"^(\d{5})(?:-\d{4})?$").unwrap();
+    static ref POSTAL_CODE: Regex = Regex::new(r"^([A-Za-z]\d[A-Za-z])\s?(\d[A-Za-z]\d)$").unwrap();
+    static ref HOUSE_NUMBER: Regex = Regex::new(r"^\d{1,6}[A-Za-z]?$").unwrap();
+    static ref CITY_WORD: Regex = Regex::new(r"^[A-Za-z][A-Za-z.'-]*$").unwrap();
+}
+
+/// A US or Canadian street address; any part may be missing
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Address {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub street: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    /// Two-letter state or province code
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// Five-digit ZIP or "A1A 1A1" postal code
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zip: Option<String>,
+}
+
+/// An address found in text, with the text as it was written
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddressMatch {
+    pub address: Address,
+    pub matched: String,
+}
+
+impl Address {
+    /// Parse one address such as a people search field, normalizing every
+    /// part ("123 MAIN STREET APT 4, PHILADELPHIA, PA 19102-1234" ->
+    /// "123 Main St Apt 4, Philadelphia, PA 19102"). Partial addresses are
+    /// fine, but there must be a street or a city.
+    pub fn parse(text: &str) -> Option<Address> {
+        let mut words: Vec<(usize, &str)> = text
+            .split(',')
+            .enumerate()
+            .flat_map(|(segment, part)| part.split_whitespace().map(move |w| (segment, w)))
+            .collect();
+        let mut address = Address::default();
+
+        // ZIP or postal code, then state or province, from the end
+        if let Some(&(_, last)) = words.last() {
+            let last = last.trim_end_matches('.');
+            if let Some(cap) = ZIP_CODE.captures(last) {
+                address.zip = Some(cap[1].to_string());
+                words.pop();
+            } else if let Some(zip) = postal_code(last) {
+                address.zip = Some(zip);
+                words.pop();
+            } else if words.len() >= 2 {
+                let pair = format!("{} {}", words[words.len() - 2].1, last);
+                if let Some(zip) = postal_code(&pair) {
+                    address.zip = Some(zip);
+                    words.truncate(words.len() - 2);
+                }
+            }
+        }
+        if let Some((count, code)) = region_at_end(&words) {
+            address.state = Some(code.to_string());
+            words.truncate(words.len() - count);
+        }
+
+        let last_segment = words.last().map_or(0, |(s, _)| *s);
+
+        match words.first() {
+            Some(&(first_segment, number)) if HOUSE_NUMBER.is_match(number) => {
+                let line: Vec<&str> = segment_of(&words, first_segment).split_off(1);
+                let end = line
+                    .iter()
+                    .enumerate()
+                    .skip(1)
+                    .find(|(_, w)| street_suffix(w).is_some())
+                    .map_or(line.len(), |(i, _)| {
+                        if line.get(i + 1).is_some_and(|w| directional(w).is_some()) {
+                            i + 2
+                        } else {
+                            i + 1
+                        }
+                    });
+                if end > 0 {
+                    address.number = Some(number.to_uppercase());
+                    address.street = Some(normalize_street(&line[..end]));
+                }
+                let mut rest = &line[end..];
+                if let Some((unit, used)) = parse_unit(rest) {
+                    address.unit = Some(unit);
+                    rest = &rest[used..];
+                }
+                let mut city = None;
+                for segment in first_segment + 1..=last_segment {
+                    let words = segment_of(&words, segment);
+                    match parse_unit(&words) {
+                        Some((unit, _)) if address.unit.is_none() => address.unit = Some(unit),
+                        _ if !words.is_empty() => city = Some(words),
+                        _ => {}
+                    }
+                }
+                // Words straight after the street are only a city when a state follows
+                if city.is_none() && !rest.is_empty() && address.state.is_some() {
+                    city = Some(rest.to_vec());
+                }
+                address.city = city.and_then(|words| normalize_city(&words));
+            }
+            Some(&(first_segment, _)) => {
+                let first = segment_of(&words, first_segment);
+                if last_segment > first_segment && first.last().is_some_and(|w| street_suffix(w).is_some()) {
+                    address.street = Some(normalize_street(&first));
+                }
+                address.city = normalize_city(&segment_of(&words, last_segment));
+            }
+            None => {}
+        }
+
+        (address.street.is_some() || address.city.is_some()).then_some(address)
+    }
+
+    /// Parts present, for telling a partial address from a fuller one
+    fn parts(&self) -> [Option<&String>; 6] {
+        [
+            self.number.as_ref(),
+            self.street.as_ref(),
+            self.unit.as_ref(),
+            self.city.as_ref(),
+            self.state.as_ref(),
+            self.zip.as_ref(),
+        ]
+    }
+
+    /// Whether every part of this address agrees with a fuller one
+    fn fits(&self, other: &Address) -> bool {
+        self.parts()
+            .iter()
+            .zip(other.parts())
+            .all(|(mine, theirs)| mine.is_none() || *mine == theirs)
+    }
+
+    fn part_count(&self) -> usize {
+        self.parts().iter().flatten().count()
+    }
+}
+
+impl fmt::Display for Address {
+    /// "123 Main St Apt 4, Philadelphia, PA 19102", skipping missing parts
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |parts: &[&Option<String>]| {
+            parts.iter().filter_map(|p| p.as_deref()).collect::<Vec<_>>().join(" ")
+        };
+        let line = join(&[&self.number, &self.street, &self.unit]);
+        let region = join(&[&self.state, &self.zip]);
+        let parts: Vec<&str> = [line.as_str(), self.city.as_deref().unwrap_or(""), region.as_str()]
+            .into_iter()
+            .filter(|p| !p.is_empty())
+            .collect();
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// Find street addresses in free text such as a snippet. Only matches that
+/// start with a house number and street are taken; a bare "City, ST" is left
+/// to `extract_locations`.
+pub fn extract_addresses(text: &str) -> Vec<AddressMatch> {
+    ADDRESS_PATTERN
+        .find_iter(text)
+        .filter_map(|m| {
+            let matched = m.as_str().trim_end_matches([',', '.']);
+            Address::parse(matched).map(|address| AddressMatch {
+                address,
+                matched: matched.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Fold each partial address ("123 Main St", "Philadelphia, PA") into the
+/// fuller address it fits, when exactly one does, then combine entries
+/// with the same normalized form
+pub fn merge_partial(entities: Vec<Entity>) -> Vec<Entity> {
+    let parsed: Vec<Option<Address>> = entities.iter().map(|e| Address::parse(&e.value)).collect();
+    let fuller = |address: &Address| -> Vec<&Address> {
+        parsed
+            .iter()
+            .flatten()
+            .filter(|other| other.part_count() > address.part_count() && address.fits(other))
+            .collect()
+    };
+    let targets: Vec<Option<String>> = parsed
+        .iter()
+        .map(|address| {
+            let candidates = fuller(address.as_ref()?);
+            // Only the fullest candidates count: "Main St" fits both
+            // "123 Main St" and "123 Main St, Dover, DE", which is one place
+            let fullest: Vec<&&Address> = candidates
+                .iter()
+                .filter(|a| !candidates.iter().any(|b| b.part_count() > a.part_count() && a.fits(b)))
+                .collect();
+            match fullest.as_slice() {
+                [only] => Some(only.to_string()),
+                _ => None,
+            }
+        })
+        .collect();
+    merge_entities(entities.into_iter().zip(targets).map(|(mut entity, target)| {
+        if let Some(target) = target {
+            entity.value = target;
+        }
+        entity
+    }))
+}
+
+/// The words of one comma-separated segment
+fn segment_of<'a>(words: &[(usize, &'a str)], segment: usize) -> Vec<&'a str> {
+    words.iter().filter(|(s, _)| *s == segment).map(|(_, w)| *w).collect()
+}
+
+fn lookup(table: &[(&str, &'static str)], word: &str) -> Option<&'static str> {
+    let word = word.trim_end_matches('.').to_lowercase();
+    table.iter().find(|(written, _)| *written == word).map(|(_, short)| *short)
+}
+
+fn street_suffix(word: &str) -> Option<&'static str> {
+    lookup(STREET_SUFFIXES, word)
+}
+
+fn directional(word: &str) -> Option<&'static str> {
+    lookup(DIRECTIONALS, word)
+}
+
+/// "A1A 1A1" from a Canadian postal code written with or without the space
+pub fn postal_code(text: &str) -> Option<String> {
+    POSTAL_CODE
+        .captures(text)
+        .map(|cap| format!("{} {}", &cap[1], &cap[2]).to_uppercase())
+}
+
+/// A state or province at the end of the words, as (words used, code).
+/// Codes must be capitals ("PA", not "Pa"); full names may be any case.
+fn region_at_end(words: &[(usize, &str)]) -> Option<(usize, &'static str)> {
+    (1..=3.min(words.len())).rev().find_map(|count| {
+        let tail: Vec<&str> = words[words.len() - count..]
+            .iter()
+            .map(|(_, w)| w.trim_end_matches('.'))
+            .collect();
+        state_code(&tail.join(" ")).map(|code| (count, code))
+    })
+}
+
+/// "Apt 4", "Apt. #4B", "Suite 200" or "#4" at the start of the words, as
+/// (normalized unit, words used)
+fn parse_unit(words: &[&str]) -> Option<(String, usize)> {
+    let first = *words.first()?;
+    if let Some(id) = first.strip_prefix('#').filter(|id| !id.is_empty()) {
+        return Some((format!("#{}", id.to_uppercase()), 1));
+    }
+    let designator = if first == "#" { "#" } else { lookup(UNIT_DESIGNATORS, first)? };
+    let id = words.get(1)?.trim_start_matches('#');
+    let id = if id.is_empty() { words.get(2)? } else { id };
+    let used = if words.get(1) == Some(&"#") { 3 } else { 2 };
+    let unit = if designator == "#" {
+        format!("#{}", id.to_uppercase())
+    } else {
+        format!("{} {}", designator, id.to_uppercase())
+    };
+    Some((unit, used))
+}
+
+/// Title-case words written in one case, keeping mixed case ("McKinley")
+/// and lowercasing ordinals ("5TH" -> "5th")
+fn title_word(word: &str) -> String {
+    let word = word.trim_end_matches(',');
+    if word.starts_with(|c: char| c.is_ascii_digit()) {
+        return word.to_lowercase();
+    }
+    let has_upper = word.chars().skip(1).any(char::is_uppercase);
+    let has_lower = word.chars().any(char::is_lowercase);
+    if has_upper && has_lower {
+        return word.to_string();
+    }
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|c| c.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect())
+        .unwrap_or_default()
+}
+
+/// "NORTH MAIN STREET" -> "N Main St", "Main Street West" -> "Main St W"
+fn normalize_street(words: &[&str]) -> String {
+    let suffix_at = words.iter().rposition(|w| street_suffix(w).is_some());
+    words
+        .iter()
+        .enumerate()
+        .map(|(i, word)| {
+            if Some(i) == suffix_at && i > 0 {
+                return street_suffix(word).unwrap().to_string();
+            }
+            let leading = i == 0 && words.len() >= 3;
+            let trailing = i > 0 && Some(i - 1) == suffix_at;
+            match directional(word) {
+                Some(short) if leading || trailing => short.to_string(),
+                _ => title_word(word.trim_end_matches('.')),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A city is one to four plain words
+fn normalize_city(words: &[&str]) -> Option<String> {
+    (!words.is_empty() && words.len() <= 4 && words.iter().all(|w| CITY_WORD.is_match(w)))
+        .then(|| words.iter().map(|w| title_word(w)).collect::<Vec<_>>().join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{EntitySource, SourceKind};
+
+    fn parsed(text: &str) -> String {
+        Address::parse(text).map(|a| a.to_string()).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_parse_addresses() {
+        let full = Address::parse("123 MAIN STREET APT 4B, PHILADELPHIA, PA 19102-1234").unwrap();
+        assert_eq!(full.number.as_deref(), Some("123"));
+        assert_eq!(full.street.as_deref(), Some("Main St"));
+        assert_eq!(full.unit.as_deref(), Some("Apt 4B"));
+        assert_eq!(full.city.as_deref(), Some("Philadelphia"));
+        assert_eq!(full.state.as_deref(), Some("PA"));
+        assert_eq!(full.zip.as_deref(), Some("19102"));
+        assert_eq!(full.to_string(), "123 Main St Apt 4B, Philadelphia, PA 19102");
+
+        assert_eq!(parsed("42 north oak avenue #7 Dover DE"), "42 N Oak Ave #7, Dover, DE");
+        assert_eq!(parsed("10 Elm St., Springfield"), "10 Elm St, Springfield");
+        assert_eq!(parsed("Austin, Texas"), "Austin, TX");
+        assert_eq!(parsed("200 King St W, Suite 5, Toronto, ON m5h3t4"), "200 King St W Ste 5, Toronto, ON M5H 3T4");
+        assert_eq!(parsed("PA 19102"), "");
+        // Round trip: the display form parses back to itself
+        assert_eq!(parsed(&full.to_string()), full.to_string());
+    }
+
+    #[test]
+    fn test_extract_addresses() {
+        let found = extract_addresses(
+            "Call 555-123-4567 at 42 Oak Ave in Dover. Owner: 123 MAIN ST, PHILADELPHIA, PA 19102 since 2010",
+        );
+        let values: Vec<String> = found.iter().map(|m| m.address.to_string()).collect();
+        assert_eq!(values, vec!["42 Oak Ave", "123 Main St, Philadelphia, PA 19102"]);
+        assert_eq!(found[1].matched, "123 MAIN ST, PHILADELPHIA, PA 19102");
+        assert!(extract_addresses("Philadelphia, PA 19102").is_empty());
+    }
+
+    #[test]
+    fn test_merge_partial_addresses() {
+        let entity = |value: &str, page: &str| {
+            let mut entity = Entity::new(value);
+            entity.add_source(EntitySource {
+                mentions: 1,
+                ..EntitySource::new(page, page, vec!["Google".to_string()], SourceKind::Web, true)
+            });
+            entity
+        };
+        let merged = merge_partial(vec![
+            entity("123 Main St, Philadelphia, PA 19102", "a"),
+            entity("123 Main St", "b"),
+            entity("Philadelphia, PA", "c"),
+            entity("9 Elm St, Dover, DE", "d"),
+            entity("9 Elm St, Dover, DE 19901", "e"),
+            entity("Dover, DE", "f"),
+        ]);
+        let values: Vec<(&str, usize)> = merged.iter().map(|e| (e.value.as_str(), e.pages)).collect();
+        assert_eq!(
+            values,
+            vec![("123 Main St, Philadelphia, PA 19102", 3), ("9 Elm St, Dover, DE 19901", 3)]
+        );
+    }
+}
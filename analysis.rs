@@ -1,21 +1,174 @@
-use crate::parser::{extract_emails, extract_locations, extract_names, extract_usernames};
+use crate::address::{self, extract_addresses, Address};
+use crate::location::{self, LocationNode, PATH_SEPARATOR};
+use crate::cluster::{cluster_names, NameCluster, Review};
+use crate::dedup::MergedResult;
+use crate::exclusion::Exclusions;
+use crate::names::{self, NameMatch};
+use crate::entity::{merge_entities, Entity, EntitySource, EntityTally, SourceKind, HIGHLIGHT};
+use crate::parser::{extract_emails, extract_locations, extract_names, extract_usernames, EntityKind};
+use crate::person::{format_table, PersonRecord};
+use crate::redact::{RedactLevel, Redactor};
 use crate::search::SearchResult;
 use colored::*;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::cmp::Reverse;
 use std::collections::HashMap;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Sources listed per entity with `--explain`
+const EXPLAIN_SOURCES: usize = 5;
+
+/// Hex characters kept when a text page key is hashed
+const TEXT_KEY_HASH_LEN: usize = 16;
+
+/// Page keys made from result text ("text:<title+snippet>") are hashed when
+/// the collection scope is restricted, so raw text isn't saved through them
+fn scoped_key(key: &str) -> String {
+    match key.strip_prefix("text:") {
+        Some(text) => format!("text-sha256:{}", &hex::encode(Sha256::digest(text.as_bytes()))[..TEXT_KEY_HASH_LEN]),
+        None => key.to_string(),
+    }
+}
+
+/// A result flagged as phone-directory spam
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpamEntry {
+    pub source: String,
+    pub title: String,
+    pub url: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PatternAnalysis {
+    /// Results as returned by the engines, counting duplicates
     pub total_results: usize,
+    /// Distinct pages after merging duplicates across engines and formats
+    pub unique_results: usize,
+    /// Pages whose text or URL contains the target number
+    pub verified_results: usize,
+    pub unverified_results: usize,
+    /// Whether unverified results were included in entity extraction
+    pub unverified_included: bool,
     pub results_by_source: HashMap<String, usize>,
-    pub common_names: Vec<(String, usize)>,
-    pub common_locations: Vec<(String, usize)>,
-    pub emails: Vec<(String, usize)>,
-    pub usernames: Vec<(String, usize)>,
+    pub results_by_template: HashMap<String, usize>,
+    pub results_by_match_mode: HashMap<String, usize>,
+    pub common_names: Vec<Entity>,
+    /// Name variants grouped into likely identities (see `cluster::cluster_names`)
+    pub name_clusters: Vec<NameCluster>,
+    pub common_locations: Vec<Entity>,
+    /// Locations and addresses rolled up by state › city › ZIP
+    pub location_hierarchy: Vec<LocationNode>,
+    /// Street addresses in normalized form, partial ones folded into fuller matches
+    pub addresses: Vec<Entity>,
+    pub emails: Vec<Entity>,
+    pub usernames: Vec<Entity>,
+    pub directory_spam: Vec<SpamEntry>,
+    /// Whether directory spam was included in entity extraction
+    pub spam_included: bool,
+    /// Structured records from people search sites
+    pub people_records: Vec<PersonRecord>,
+    /// Redaction applied to this copy (see `redacted`)
+    pub redaction: RedactLevel,
+    /// Entity types that were extracted; others were never collected
+    pub collected: Vec<EntityKind>,
 }
 
 impl PatternAnalysis {
-    pub fn print_summary(&self, no_color: bool) {
+    /// Copy with personal details hidden for sharing. Entries that become
+    /// identical (e.g. two names with the same initials) are combined.
+    pub fn redacted(&self, redactor: &Redactor) -> PatternAnalysis {
+        let mut redacted = self.clone();
+        redacted.redaction = redactor.level();
+        let redact_entity = |entity: &Entity, value: String| {
+            let mut entity = entity.clone();
+            entity.value = value;
+            for source in &mut entity.sources {
+                source.key = redactor.text(&source.key);
+                source.url = redactor.text(&source.url);
+                source.excerpt = redactor.text(&source.excerpt);
+            }
+            entity
+        };
+        redacted.common_names = merge_entities(
+            self.common_names.iter().map(|e| redact_entity(e, redactor.name(&e.value))),
+        );
+        redacted.name_clusters = self
+            .name_clusters
+            .iter()
+            .map(|cluster| {
+                let label = redactor.name(&cluster.label);
+                let mut members: Vec<String> = Vec::new();
+                for member in cluster.members.iter().map(|m| redactor.name(m)) {
+                    if !members.contains(&member) {
+                        members.push(member);
+                    }
+                }
+                NameCluster {
+                    evidence: redact_entity(&cluster.evidence, label.clone()),
+                    label,
+                    members,
+                }
+            })
+            .collect();
+        redacted.common_locations = merge_entities(
+            self.common_locations
+                .iter()
+                .filter_map(|e| redactor.location(&e.value).map(|l| redact_entity(e, l))),
+        );
+        location::retain_nodes(&mut redacted.location_hierarchy, &|name| redactor.location(name).is_some());
+        redacted.addresses = merge_entities(
+            self.addresses.iter().map(|e| redact_entity(e, redactor.address(&e.value))),
+        );
+        redacted.emails = merge_entities(
+            self.emails.iter().map(|e| redact_entity(e, redactor.email(&e.value))),
+        );
+        redacted.usernames = merge_entities(
+            self.usernames.iter().map(|e| redact_entity(e, e.value.clone())),
+        );
+        for entry in &mut redacted.directory_spam {
+            entry.title = redactor.text(&entry.title);
+            entry.url = redactor.text(&entry.url);
+            entry.reason = redactor.text(&entry.reason);
+        }
+        redacted.people_records = self.people_records.iter().map(|r| redactor.person(r)).collect();
+        redacted
+    }
+
+    /// Whether some data types were left out of collection
+    pub fn is_scoped(&self) -> bool {
+        self.collected.len() < EntityKind::ALL.len()
+    }
+
+    /// Collected entity types as "names, emails", or None when everything was
+    pub fn scope_label(&self) -> Option<String> {
+        self.is_scoped()
+            .then(|| self.collected.iter().map(EntityKind::as_str).collect::<Vec<_>>().join(", "))
+    }
+
+    /// A result as it may be saved. When the scope leaves some data types
+    /// out, the raw title and snippet, which could hold them, are dropped.
+    pub fn scoped_result(&self, result: &SearchResult) -> SearchResult {
+        let mut scoped = result.clone();
+        if self.is_scoped() {
+            scoped.title.clear();
+            scoped.snippet.clear();
+        }
+        scoped
+    }
+
+    /// A merged page as it may be saved (see `scoped_result`)
+    pub fn scoped_page(&self, page: &MergedResult) -> MergedResult {
+        let mut scoped = page.clone();
+        if self.is_scoped() {
+            scoped.key = scoped_key(&page.key);
+        }
+        scoped.result = self.scoped_result(&page.result);
+        scoped
+    }
+
+    /// Print the summary; `explain` lists the evidence behind each entity
+    pub fn print_summary(&self, no_color: bool, explain: bool) {
         let separator = "=".repeat(80);
 
         if no_color {
@@ -29,14 +182,56 @@ impl PatternAnalysis {
         }
         println!();
 
+        let unverified_note = if self.unverified_included {
+            "included in analysis"
+        } else {
+            "excluded from analysis"
+        };
         if no_color {
             println!("Total Results Found: {}", self.total_results);
+            println!(
+                "Unique Pages: {} ({} duplicates merged)",
+                self.unique_results,
+                self.total_results - self.unique_results
+            );
+            println!(
+                "Verified (mention the number): {} | Unverified: {} ({})",
+                self.verified_results, self.unverified_results, unverified_note
+            );
         } else {
             println!(
                 "{} {}",
                 "Total Results Found:".cyan(),
                 self.total_results
             );
+            println!(
+                "{} {} ({} duplicates merged)",
+                "Unique Pages:".cyan(),
+                self.unique_results,
+                self.total_results - self.unique_results
+            );
+            println!(
+                "{} {} | {} {} ({})",
+                "Verified (mention the number):".cyan(),
+                self.verified_results.to_string().green(),
+                "Unverified:".cyan(),
+                self.unverified_results.to_string().yellow(),
+                unverified_note
+            );
+        }
+        if let Some(scope) = self.scope_label() {
+            if no_color {
+                println!("Collected: {}", scope);
+            } else {
+                println!("{} {}", "Collected:".cyan(), scope.yellow());
+            }
+        }
+        if self.redaction != RedactLevel::None {
+            if no_color {
+                println!("Redaction: {}", self.redaction.as_str());
+            } else {
+                println!("{} {}", "Redaction:".cyan(), self.redaction.as_str().yellow());
+            }
         }
         println!();
 
@@ -57,6 +252,57 @@ impl PatternAnalysis {
             println!();
         }
 
+        // Query template yield
+        if !self.results_by_template.is_empty() {
+            if no_color {
+                println!("Results by Query Template:");
+            } else {
+                println!("{}", "Results by Query Template:".blue().bold());
+            }
+            let mut templates: Vec<(&String, &usize)> = self.results_by_template.iter().collect();
+            templates.sort_by_key(|(name, count)| (Reverse(**count), *name));
+            for (template, count) in templates {
+                if no_color {
+                    println!("  - {}: {} results", template, count);
+                } else {
+                    println!("  • {}: {} results", template.green(), count);
+                }
+            }
+            println!();
+        }
+
+        // Match mode breakdown (only interesting when several modes ran)
+        if self.results_by_match_mode.len() > 1 {
+            if no_color {
+                println!("Results by Match Mode:");
+            } else {
+                println!("{}", "Results by Match Mode:".blue().bold());
+            }
+            let mut modes: Vec<(&String, &usize)> = self.results_by_match_mode.iter().collect();
+            modes.sort();
+            for (mode, count) in modes {
+                if no_color {
+                    println!("  - {}: {} results", mode, count);
+                } else {
+                    println!("  • {}: {} results", mode.green(), count);
+                }
+            }
+            println!();
+        }
+
+        // People search records
+        if !self.people_records.is_empty() {
+            if no_color {
+                println!("People Search Records:");
+            } else {
+                println!("{}", "👥 People Search Records:".blue().bold());
+            }
+            for line in format_table(&self.people_records).lines() {
+                println!("  {}", line);
+            }
+            println!();
+        }
+
         // Name patterns
         if !self.common_names.is_empty() {
             if no_color {
@@ -64,15 +310,18 @@ impl PatternAnalysis {
             } else {
                 println!("{}", "📛 Names Found:".blue().bold());
             }
-            for (name, count) in &self.common_names {
+            for name in &self.common_names {
                 if no_color {
-                    println!("  - {}: mentioned {} time(s)", name, count);
+                    println!("  - {}: {}", name.value, name.summary());
                 } else {
-                    println!("  • {}: mentioned {} time(s)", name.green(), count);
+                    println!("  • {}: {}", name.value.green(), name.summary());
+                }
+                if explain {
+                    print_evidence(name, no_color);
                 }
             }
             println!();
-        } else {
+        } else if self.collected.contains(&EntityKind::Names) {
             if no_color {
                 println!("No names detected in search results\n");
             } else {
@@ -80,6 +329,28 @@ impl PatternAnalysis {
             }
         }
 
+        // Name variants that look like the same person
+        let identities: Vec<&NameCluster> = self.name_clusters.iter().filter(|c| c.members.len() > 1).collect();
+        if !identities.is_empty() {
+            if no_color {
+                println!("Identities (name variants combined):");
+            } else {
+                println!("{}", "🧩 Identities (name variants combined):".blue().bold());
+            }
+            for cluster in identities {
+                if no_color {
+                    println!("  - {}: {}", cluster.label, cluster.evidence.summary());
+                } else {
+                    println!("  • {}: {}", cluster.label.green(), cluster.evidence.summary());
+                }
+                println!("      spellings: {}", cluster.members.join(", "));
+                if explain {
+                    print_evidence(&cluster.evidence, no_color);
+                }
+            }
+            println!();
+        }
+
         // Location patterns
         if !self.common_locations.is_empty() {
             if no_color {
@@ -87,15 +358,18 @@ impl PatternAnalysis {
             } else {
                 println!("{}", "📍 Locations Mentioned:".blue().bold());
             }
-            for (location, count) in &self.common_locations {
+            for location in &self.common_locations {
                 if no_color {
-                    println!("  - {}: {} occurrence(s)", location, count);
+                    println!("  - {}: {}", location.value, location.summary());
                 } else {
-                    println!("  • {}: {} occurrence(s)", location.green(), count);
+                    println!("  • {}: {}", location.value.green(), location.summary());
+                }
+                if explain {
+                    print_evidence(location, no_color);
                 }
             }
             println!();
-        } else {
+        } else if self.collected.contains(&EntityKind::Locations) {
             if no_color {
                 println!("No locations detected in search results\n");
             } else {
@@ -103,6 +377,38 @@ impl PatternAnalysis {
             }
         }
 
+        if !self.location_hierarchy.is_empty() {
+            if no_color {
+                println!("Location Hierarchy:");
+            } else {
+                println!("{}", "🗺️  Location Hierarchy:".blue().bold());
+            }
+            for state in &self.location_hierarchy {
+                print_location(state, 0, no_color);
+            }
+            println!();
+        }
+
+        // Street addresses
+        if !self.addresses.is_empty() {
+            if no_color {
+                println!("Addresses Found:");
+            } else {
+                println!("{}", "🏠 Addresses Found:".blue().bold());
+            }
+            for address in &self.addresses {
+                if no_color {
+                    println!("  - {}: {}", address.value, address.summary());
+                } else {
+                    println!("  • {}: {}", address.value.green(), address.summary());
+                }
+                if explain {
+                    print_evidence(address, no_color);
+                }
+            }
+            println!();
+        }
+
         // Email patterns
         if !self.emails.is_empty() {
             if no_color {
@@ -110,11 +416,14 @@ impl PatternAnalysis {
             } else {
                 println!("{}", "📧 Emails Found:".blue().bold());
             }
-            for (email, count) in &self.emails {
+            for email in &self.emails {
                 if no_color {
-                    println!("  - {}: {} occurrence(s)", email, count);
+                    println!("  - {}: {}", email.value, email.summary());
                 } else {
-                    println!("  • {}: {} occurrence(s)", email.green(), count);
+                    println!("  • {}: {}", email.value.green(), email.summary());
+                }
+                if explain {
+                    print_evidence(email, no_color);
                 }
             }
             println!();
@@ -127,11 +436,39 @@ impl PatternAnalysis {
             } else {
                 println!("{}", "👤 Usernames/Social Media Found:".blue().bold());
             }
-            for (username, count) in &self.usernames {
+            for username in &self.usernames {
+                if no_color {
+                    println!("  - @{}: {}", username.value, username.summary());
+                } else {
+                    println!("  • @{}: {}", username.value.green(), username.summary());
+                }
+                if explain {
+                    print_evidence(username, no_color);
+                }
+            }
+            println!();
+        }
+
+        // Directory spam
+        if !self.directory_spam.is_empty() {
+            let heading = if self.spam_included {
+                format!("Directory Spam ({} results, included in analysis):", self.directory_spam.len())
+            } else {
+                format!("Directory Spam ({} results, excluded from analysis):", self.directory_spam.len())
+            };
+            if no_color {
+                println!("{}", heading);
+            } else {
+                println!("{}", format!("🚫 {}", heading).blue().bold());
+            }
+            for entry in &self.directory_spam {
                 if no_color {
-                    println!("  - @{}: {} occurrence(s)", username, count);
+                    println!("  - [{}] {} ({})", entry.source, entry.title, entry.reason);
                 } else {
-                    println!("  • @{}: {} occurrence(s)", username.green(), count);
+                    println!("  • [{}] {} {}", entry.source, entry.title, format!("({})", entry.reason).yellow());
+                }
+                if !entry.url.is_empty() {
+                    println!("      {}", entry.url);
                 }
             }
             println!();
@@ -154,19 +491,26 @@ impl PatternAnalysis {
                 );
             }
         } else {
-            if let Some((name, _)) = self.common_names.first() {
+            if let Some(cluster) = self.name_clusters.first() {
+                let name = &cluster.evidence;
                 if no_color {
-                    println!("  - Most associated name: {}", name);
+                    println!("  - Most associated name: {} (confidence {:.2})", name.value, name.confidence);
                 } else {
-                    println!("  • {}: {}", "Most associated name".green(), name);
+                    println!("  • {}: {} (confidence {:.2})", "Most associated name".green(), name.value, name.confidence);
                 }
             }
 
-            if let Some((location, _)) = self.common_locations.first() {
+            if let Some(state) = self.location_hierarchy.first() {
+                if no_color {
+                    println!("  - Most associated location: {}", state.top_path());
+                } else {
+                    println!("  • {}: {}", "Most associated location".green(), state.top_path());
+                }
+            } else if let Some(location) = self.common_locations.first() {
                 if no_color {
-                    println!("  - Most associated location: {}", location);
+                    println!("  - Most associated location: {} (confidence {:.2})", location.value, location.confidence);
                 } else {
-                    println!("  • {}: {}", "Most associated location".green(), location);
+                    println!("  • {}: {} (confidence {:.2})", "Most associated location".green(), location.value, location.confidence);
                 }
             }
 
@@ -192,11 +536,24 @@ impl PatternAnalysis {
     pub fn to_json(&self) -> serde_json::Value {
         serde_json::json!({
             "total_results": self.total_results,
+            "unique_results": self.unique_results,
+            "verified_results": self.verified_results,
+            "unverified_results": self.unverified_results,
+            "unverified_included": self.unverified_included,
             "results_by_source": self.results_by_source,
+            "results_by_template": self.results_by_template,
+            "results_by_match_mode": self.results_by_match_mode,
             "common_names": self.common_names,
+            "name_clusters": self.name_clusters,
             "common_locations": self.common_locations,
+            "location_hierarchy": self.location_hierarchy,
+            "addresses": self.addresses,
             "emails": self.emails,
             "usernames": self.usernames,
+            "directory_spam": self.directory_spam,
+            "spam_included": self.spam_included,
+            "redaction": self.redaction,
+            "collected": self.collected,
         })
     }
 
@@ -212,99 +569,454 @@ impl PatternAnalysis {
 
     /// Get unique usernames for external tool integration
     pub fn get_usernames(&self) -> Vec<String> {
-        self.usernames.iter().map(|(u, _)| u.clone()).collect()
+        self.usernames.iter().map(|u| u.value.clone()).collect()
     }
 
     /// Get unique emails for external tool integration
     pub fn get_emails(&self) -> Vec<String> {
-        self.emails.iter().map(|(e, _)| e.clone()).collect()
+        self.emails.iter().map(|e| e.value.clone()).collect()
+    }
+}
+
+/// Print where an entity was found: source, URL and highlighted excerpt
+fn print_evidence(entity: &Entity, no_color: bool) {
+    if let Some(likelihood) = entity.likelihood {
+        println!("      name likelihood {:.2}", likelihood);
+    }
+    for source in entity.sources.iter().take(EXPLAIN_SOURCES) {
+        let engines = source.engines.join(", ");
+        if no_color {
+            println!("      [{}] {}", engines, source.url);
+        } else {
+            println!("      [{}] {}", engines.cyan(), source.url.dimmed());
+        }
+        if !source.excerpt.is_empty() {
+            let excerpt = if no_color {
+                source.excerpt.clone()
+            } else {
+                highlight(&source.excerpt)
+            };
+            println!("        \"{}\"", excerpt);
+        }
+    }
+    if entity.sources.len() > EXPLAIN_SOURCES {
+        println!("      … and {} more source(s)", entity.sources.len() - EXPLAIN_SOURCES);
+    }
+}
+
+/// One hierarchy node and the places inside it, indented by level
+fn print_location(node: &LocationNode, depth: usize, no_color: bool) {
+    let indent = "    ".repeat(depth);
+    if depth == 0 {
+        if no_color {
+            println!("  - {}", node.label());
+        } else {
+            println!("  • {} ({})", node.name.green(), node.count);
+        }
+    } else {
+        println!("  {}{}{}", indent, PATH_SEPARATOR.trim_start(), node.label());
     }
+    for child in &node.children {
+        print_location(child, depth + 1, no_color);
+    }
+}
+
+/// Swap the `**` markers in an excerpt for terminal highlighting
+fn highlight(excerpt: &str) -> String {
+    excerpt
+        .split(HIGHLIGHT)
+        .enumerate()
+        .map(|(i, part)| if i % 2 == 1 { part.yellow().bold().to_string() } else { part.to_string() })
+        .collect()
 }
 
-pub struct PatternAnalyzer;
+pub struct PatternAnalyzer {
+    include_spam: bool,
+    include_unverified: bool,
+    collect: Vec<EntityKind>,
+    min_confidence: f64,
+    review: Review,
+    exclusions: Exclusions,
+}
+
+impl Default for PatternAnalyzer {
+    fn default() -> Self {
+        PatternAnalyzer {
+            include_spam: false,
+            include_unverified: false,
+            collect: EntityKind::ALL.to_vec(),
+            min_confidence: 0.0,
+            review: Review::default(),
+            exclusions: Exclusions::default(),
+        }
+    }
+}
 
 impl PatternAnalyzer {
     pub fn new() -> Self {
-        PatternAnalyzer
+        PatternAnalyzer::default()
+    }
+
+    /// Only run the extractors for these data types (empty means all).
+    /// People record fields outside the scope are dropped too, and saved
+    /// copies of results lose their raw text (see `PatternAnalysis::scoped_result`).
+    pub fn collect(mut self, kinds: &[EntityKind]) -> Self {
+        let mut kinds = if kinds.is_empty() { EntityKind::ALL.to_vec() } else { kinds.to_vec() };
+        kinds.sort();
+        kinds.dedup();
+        self.collect = kinds;
+        self
+    }
+
+    fn collects(&self, kind: EntityKind) -> bool {
+        self.collect.contains(&kind)
     }
 
-    pub fn analyze(&self, all_results: &HashMap<String, Vec<SearchResult>>, max_names: usize, max_locations: usize, max_emails: usize, max_usernames: usize) -> PatternAnalysis {
-        let mut all_text = Vec::new();
+    /// Drop entities scoring below this confidence (0-1)
+    pub fn min_confidence(mut self, min: f64) -> Self {
+        self.min_confidence = min;
+        self
+    }
+
+    /// Apply an analyst's merge/split decisions when clustering names
+    pub fn review(mut self, review: Review) -> Self {
+        self.review = review;
+        self
+    }
+
+    /// Words, usernames and emails to leave out (default: the built-in lists)
+    pub fn exclusions(mut self, exclusions: Exclusions) -> Self {
+        self.exclusions = exclusions;
+        self
+    }
+
+    /// Include results flagged as directory spam in entity extraction
+    pub fn include_spam(mut self, include: bool) -> Self {
+        self.include_spam = include;
+        self
+    }
+
+    /// Include results that don't mention the target number in entity extraction
+    pub fn include_unverified(mut self, include: bool) -> Self {
+        self.include_unverified = include;
+        self
+    }
+
+    /// Analyze deduplicated pages (see `dedup::merge_results`). Entities are
+    /// extracted from each page on its own, so every entity knows which pages
+    /// and engines it came from.
+    pub fn analyze(&self, pages: &[MergedResult], people: &[PersonRecord], max_names: usize, max_locations: usize, max_emails: usize, max_usernames: usize) -> PatternAnalysis {
+        let mut total_results = 0;
+        let mut verified_results = 0;
         let mut source_counts: HashMap<String, usize> = HashMap::new();
+        let mut template_counts: HashMap<String, usize> = HashMap::new();
+        let mut match_mode_counts: HashMap<String, usize> = HashMap::new();
+        let mut directory_spam = Vec::new();
+
+        let collect_names = self.collects(EntityKind::Names);
+        let collect_locations = self.collects(EntityKind::Locations);
+        let collect_emails = self.collects(EntityKind::Emails);
+        let collect_usernames = self.collects(EntityKind::Usernames);
+        let scoped = self.collect.len() < EntityKind::ALL.len();
+        let mut names = EntityTally::new();
+        let mut locations = EntityTally::new();
+        let mut addresses = EntityTally::new();
+        let mut emails = EntityTally::new();
+        let mut usernames = EntityTally::new();
+
+        // Count every hit, then extract each page's entities once
+        for page in pages {
+            for hit in &page.hits {
+                *source_counts.entry(hit.engine.clone()).or_insert(0) += 1;
+                if !hit.template.is_empty() {
+                    *template_counts.entry(hit.template.clone()).or_insert(0) += 1;
+                }
+                if let Some(mode) = hit.match_mode {
+                    *match_mode_counts.entry(mode.as_str().to_string()).or_insert(0) += 1;
+                }
+                total_results += 1;
+            }
 
-        // Collect all text and count sources
-        for results in all_results.values() {
-            for result in results {
-                let text = format!("{} {}", result.title, result.snippet);
-                all_text.push(text);
-                *source_counts.entry(result.source.clone()).or_insert(0) += 1;
+            let result = &page.result;
+            if result.verified {
+                verified_results += 1;
             }
-        }
 
-        // Combine all text
-        let combined_text = all_text.join(" ");
+            if result.directory_spam {
+                directory_spam.push(SpamEntry {
+                    source: page.engines().join(", "),
+                    title: if scoped { String::new() } else { result.title.clone() },
+                    url: result.url.clone(),
+                    reason: result.spam_reason.clone().unwrap_or_default(),
+                });
+                if !self.include_spam {
+                    continue;
+                }
+            }
+            if !result.verified && !self.include_unverified {
+                continue;
+            }
 
-        // Extract names, locations, emails, and usernames
-        let names = extract_names(&combined_text);
-        let locations = extract_locations(&combined_text);
-        let emails_list = extract_emails(&combined_text);
-        let usernames_list = extract_usernames(&combined_text);
+            // Title and snippet are scanned separately so no match spans the two
+            let kind = if result.directory_spam { SourceKind::Directory } else { SourceKind::Web };
+            let key = if scoped { scoped_key(&page.key) } else { page.key.clone() };
+            let source = EntitySource::new(&key, &result.url, page.engines(), kind, result.verified);
+            let texts = [result.title.as_str(), result.snippet.as_str()];
+            // Snippets give the most useful context, so look there first. Excerpts
+            // are raw text, so none are kept when the scope leaves data out.
+            let evidence: Vec<&str> = if scoped {
+                Vec::new()
+            } else {
+                vec![result.snippet.as_str(), result.title.as_str()]
+            };
+            let extract = |extractor: &dyn Fn(&str) -> Vec<String>| -> Vec<String> {
+                texts.iter().flat_map(|text| extractor(text)).collect()
+            };
+            if collect_names {
+                let found: Vec<NameMatch> = texts.iter().flat_map(|text| extract_names(text, &self.exclusions)).collect();
+                names.add_names(&found, &source, &evidence);
+            }
+            if collect_locations {
+                locations.add(&extract(&extract_locations), &source, &evidence);
+                let found: Vec<(String, String)> = texts
+                    .iter()
+                    .flat_map(|text| extract_addresses(text))
+                    .map(|m| (m.address.to_string(), m.matched))
+                    .collect();
+                addresses.add_written(&found, &source, &evidence);
+            }
+            if collect_emails {
+                emails.add(&extract(&|text| extract_emails(text, &self.exclusions)), &source, &evidence);
+            }
+            if collect_usernames {
+                usernames.add(&extract(&|text| extract_usernames(text, &self.exclusions)), &source, &evidence);
+            }
+        }
 
-        // Count occurrences
-        let name_counts = count_occurrences(&names);
-        let location_counts = count_occurrences(&locations);
-        let email_counts = count_occurrences(&emails_list);
-        let username_counts = count_occurrences(&usernames_list);
+        // People search records are already structured, so take their fields directly
+        let mut people_records = Vec::with_capacity(people.len());
+        for record in people {
+            *source_counts.entry(record.source.clone()).or_insert(0) += 1;
+            let mut record = record.clone();
+            if !collect_names {
+                record.full_name.clear();
+            }
+            if !self.collects(EntityKind::Ages) {
+                record.age_range = None;
+            }
+            if !collect_locations {
+                record.current_address = None;
+                record.past_addresses.clear();
+            }
+            if !self.collects(EntityKind::Phones) {
+                record.associated_numbers.clear();
+                record.carrier = None;
+                record.line_type = None;
+            }
 
-        // Sort by frequency and take top N (configurable)
-        let mut common_names: Vec<(String, usize)> = name_counts.into_iter().collect();
-        common_names.sort_by(|a, b| b.1.cmp(&a.1));
-        common_names.truncate(max_names);
+            let key = if record.source_url.is_empty() {
+                format!("{}:{}", record.source, record.full_name)
+            } else {
+                record.source_url.clone()
+            };
+            // Records are looked up by the number itself, so count as verified
+            let source = EntitySource::new(&key, &record.source_url, vec![record.source.clone()], SourceKind::PeopleSearch, true);
+            let record_text = std::iter::once(record.full_name.as_str())
+                .chain(record.addresses())
+                .filter(|field| !field.is_empty())
+                .collect::<Vec<_>>()
+                .join(" · ");
+            if !record.full_name.is_empty() {
+                let name = names::canonical(&record.full_name);
+                let found = NameMatch {
+                    likelihood: names::likelihood(&name),
+                    name,
+                    matched: record.full_name.clone(),
+                };
+                names.add_names(&[found], &source, &[&record_text]);
+            }
+            let record_locations: Vec<String> = record
+                .addresses()
+                .into_iter()
+                .flat_map(extract_locations)
+                .collect();
+            locations.add(&record_locations, &source, &[&record_text]);
+            let record_addresses: Vec<(String, String)> = record
+                .addresses()
+                .into_iter()
+                .filter_map(|field| Address::parse(field).map(|a| (a.to_string(), field.to_string())))
+                .collect();
+            addresses.add_written(&record_addresses, &source, &[&record_text]);
+            people_records.push(record);
+        }
 
-        let mut common_locations: Vec<(String, usize)> = location_counts.into_iter().collect();
-        common_locations.sort_by(|a, b| b.1.cmp(&a.1));
+        // Cluster every name so low-scoring variants still lend their evidence
+        let all_names = names.into_ranked(usize::MAX, 0.0);
+        let mut name_clusters = cluster_names(&all_names, &self.review);
+        name_clusters.retain(|c| c.evidence.confidence >= self.min_confidence);
+        name_clusters.truncate(max_names);
+        let common_names: Vec<Entity> = all_names
+            .into_iter()
+            .filter(|e| e.confidence >= self.min_confidence)
+            .take(max_names)
+            .collect();
+        let mut common_locations = locations.into_ranked(usize::MAX, self.min_confidence);
+        let mut addresses = address::merge_partial(addresses.into_ranked(usize::MAX, 0.0));
+        addresses.retain(|e| e.confidence >= self.min_confidence);
+        // The hierarchy rolls up every location, not just the ones listed
+        let location_hierarchy = location::build_hierarchy(common_locations.iter().chain(&addresses));
         common_locations.truncate(max_locations);
-
-        let mut emails: Vec<(String, usize)> = email_counts.into_iter().collect();
-        emails.sort_by(|a, b| b.1.cmp(&a.1));
-        emails.truncate(max_emails);
-
-        let mut usernames: Vec<(String, usize)> = username_counts.into_iter().collect();
-        usernames.sort_by(|a, b| b.1.cmp(&a.1));
-        usernames.truncate(max_usernames);
+        addresses.truncate(max_locations);
+        let emails = emails.into_ranked(max_emails, self.min_confidence);
+        let usernames = usernames.into_ranked(max_usernames, self.min_confidence);
 
         PatternAnalysis {
-            total_results: all_text.len(),
+            total_results,
+            unique_results: pages.len(),
+            verified_results,
+            unverified_results: pages.len() - verified_results,
+            unverified_included: self.include_unverified,
             results_by_source: source_counts,
+            results_by_template: template_counts,
+            results_by_match_mode: match_mode_counts,
             common_names,
+            name_clusters,
             common_locations,
+            location_hierarchy,
+            addresses,
             emails,
             usernames,
+            directory_spam,
+            spam_included: self.include_spam,
+            people_records,
+            redaction: RedactLevel::None,
+            collected: self.collect.clone(),
         }
     }
 }
 
-fn count_occurrences(items: &[String]) -> HashMap<String, usize> {
-    let mut counts = HashMap::new();
-    for item in items {
-        *counts.entry(item.clone()).or_insert(0) += 1;
-    }
-    counts
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dedup::merge_results;
+
+    fn values(entities: &[Entity]) -> Vec<(&str, usize)> {
+        entities.iter().map(|e| (e.value.as_str(), e.mentions)).collect()
+    }
+
+    fn verified(title: &str, snippet: &str, url: &str, engine: &str) -> SearchResult {
+        let mut result = SearchResult::new(title.to_string(), snippet.to_string(), engine.to_string());
+        result.url = url.to_string();
+        result.verified = true;
+        result
+    }
+
+    #[test]
+    fn test_entities_track_their_pages() {
+        let mut all_results = HashMap::new();
+        all_results.insert("q1".to_string(), vec![
+            verified("John Smith - Philadelphia", "555-555-1212 - John Smith, Philadelphia", "https://a.com/1", "Google"),
+            verified("Reverse lookup", "Owner: John Smith, 555-555-1212", "https://b.com/2", "Bing"),
+        ]);
+        all_results.insert("q2".to_string(), vec![
+            verified("John Smith - Philadelphia", "555-555-1212 - John Smith, Philadelphia", "https://a.com/1", "Bing"),
+        ]);
+        let pages = merge_results(&all_results);
+
+        let analysis = PatternAnalyzer::new().analyze(&pages, &[], 10, 10, 10, 10);
+        let john = &analysis.common_names[0];
+        assert_eq!(john.value, "John Smith");
+        assert_eq!((john.mentions, john.pages, john.engines), (3, 2, 2));
+        let page_a = john.sources.iter().find(|s| s.url == "https://a.com/1").unwrap();
+        assert_eq!(page_a.mentions, 2);
+        assert_eq!(page_a.engines.len(), 2);
+    }
 
     #[test]
-    fn test_count_occurrences() {
-        let items = vec![
-            "John Smith".to_string(),
+    fn test_analyze_excludes_unverified_by_default() {
+        let verified = verified("John Smith 555-555-1212", "", "", "Google");
+        let unverified = SearchResult::new(
             "Jane Doe".to_string(),
-            "John Smith".to_string(),
-        ];
-        let counts = count_occurrences(&items);
-        assert_eq!(counts.get("John Smith"), Some(&2));
-        assert_eq!(counts.get("Jane Doe"), Some(&1));
+            String::new(),
+            "Bing".to_string(),
+        );
+
+        let mut all_results = HashMap::new();
+        all_results.insert("query".to_string(), vec![verified.clone(), unverified]);
+        all_results.insert("other query".to_string(), vec![verified]);
+        let pages = merge_results(&all_results);
+
+        let analysis = PatternAnalyzer::new().analyze(&pages, &[], 10, 10, 10, 10);
+        assert_eq!(analysis.total_results, 3);
+        assert_eq!(analysis.unique_results, 2);
+        assert_eq!(analysis.verified_results, 1);
+        assert_eq!(values(&analysis.common_names), vec![("John Smith", 1)]);
+
+        let analysis = PatternAnalyzer::new()
+            .include_unverified(true)
+            .analyze(&pages, &[], 10, 10, 10, 10);
+        assert_eq!(analysis.common_names.len(), 2);
+    }
+
+    #[test]
+    fn test_analyze_uses_people_records() {
+        let mut record = PersonRecord::new("Jane Doe", "Whitepages", "https://example.com/");
+        record.current_address = Some("12 Oak Ave, Philadelphia, PA 19102".to_string());
+
+        let analysis = PatternAnalyzer::new().analyze(&[], &[record], 10, 10, 10, 10);
+        assert_eq!(values(&analysis.common_names), vec![("Jane Doe", 1)]);
+        assert!(values(&analysis.common_locations).contains(&("Philadelphia, PA", 1)));
+        assert_eq!(values(&analysis.addresses), vec![("12 Oak Ave, Philadelphia, PA 19102", 1)]);
+        assert_eq!(analysis.location_hierarchy[0].top_path(), "Pennsylvania (1) › Philadelphia (1) › 19102 (1)");
+        assert_eq!(analysis.common_names[0].sources[0].engines, vec!["Whitepages".to_string()]);
+        assert_eq!(analysis.results_by_source.get("Whitepages"), Some(&1));
+        assert_eq!(analysis.people_records.len(), 1);
+    }
+
+    #[test]
+    fn test_collect_skips_out_of_scope_entities() {
+        let mut record = PersonRecord::new("Jane Doe", "Whitepages", "https://example.com/");
+        record.current_address = Some("12 Oak Ave, Philadelphia, PA 19102".to_string());
+        record.age_range = Some("40-44".to_string());
+        record.associated_numbers = vec!["215-555-0100".to_string()];
+        record.carrier = Some("Verizon".to_string());
+        let mut result = SearchResult::new("Jane Doe, 42".to_string(), "jane@x.org 215-555-0100, Dover, DE".to_string(), "Google".to_string());
+        result.verified = true;
+        let pages = merge_results(&HashMap::from([("q".to_string(), vec![result])]));
+
+        let analysis = PatternAnalyzer::new()
+            .collect(&[EntityKind::Locations])
+            .analyze(&pages, &[record.clone()], 10, 10, 10, 10);
+        assert!(analysis.common_names.is_empty());
+        let saved = &analysis.people_records[0];
+        assert!(saved.full_name.is_empty() && saved.age_range.is_none());
+        assert!(saved.associated_numbers.is_empty() && saved.carrier.is_none());
+        assert!(!analysis.common_locations.is_empty());
+        assert_eq!(analysis.scope_label().as_deref(), Some("locations"));
+
+        // Excerpts would carry the email and number around each location
+        let excerpts: Vec<&str> = analysis
+            .common_locations
+            .iter()
+            .chain(&analysis.addresses)
+            .flat_map(|e| e.sources.iter().map(|s| s.excerpt.as_str()))
+            .collect();
+        assert!(!excerpts.is_empty());
+        assert!(excerpts.iter().all(|e| !e.contains("jane@x.org") && !e.contains("555-0100") && !e.contains("Jane")), "{:?}", excerpts);
+
+        // Raw text isn't saved under a restricted scope, not even through the page key
+        let page = analysis.scoped_page(&pages[0]);
+        assert!(page.result.title.is_empty() && page.result.snippet.is_empty());
+        assert!(page.key.starts_with("text-sha256:") && !page.key.contains("jane"));
+
+        let analysis = PatternAnalyzer::new()
+            .collect(&[EntityKind::Names, EntityKind::Ages])
+            .analyze(&[], &[record], 10, 10, 10, 10);
+        assert_eq!(analysis.people_records[0].age_range.as_deref(), Some("40-44"));
+        assert!(analysis.people_records[0].current_address.is_none());
+
+        let everything = PatternAnalyzer::new().analyze(&pages, &[], 1, 1, 1, 1);
+        assert_eq!(everything.scope_label(), None);
+        assert_eq!(everything.scoped_page(&pages[0]).result.title, "Jane Doe, 42");
     }
 }
@@ -0,0 +1,365 @@
+use crate::config::config_dir;
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// `prev_hash` of the first entry in a log
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Bytes of the per-install key numbers are hashed with
+const KEY_LEN: usize = 32;
+
+/// One line of the audit log.
+///
+/// Entries are chained: each stores the hash of the previous entry and its
+/// own hash over every other field, so edits, insertions and deletions
+/// break the chain.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position in the log, starting at 1
+    pub seq: u64,
+    pub timestamp: String,
+    pub prev_hash: String,
+    /// What was attempted (e.g. "lookup")
+    pub event: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub case_id: Option<String>,
+    /// Normalized number, or "hmac-sha256:<hex>" when numbers are hashed
+    pub number: String,
+    /// Search engines contacted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub engines: Vec<String>,
+    /// People search sites contacted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sites: Vec<String>,
+    /// Search engine requests, including retries
+    #[serde(default)]
+    pub engine_requests: usize,
+    /// Site requests, including retries
+    #[serde(default)]
+    pub site_requests: usize,
+    /// Files written (or, for a purge, deleted) by the run
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+    /// "completed", "blocked: ...", "refused: ..." or "error: ..."
+    pub outcome: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub hash: String,
+}
+
+impl AuditEntry {
+    pub fn new(event: &str, number: &str) -> Self {
+        AuditEntry {
+            timestamp: Utc::now().to_rfc3339(),
+            event: event.to_string(),
+            number: number.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Record the requests made to a search engine
+    pub fn contacted_engine(&mut self, engine: &str, requests: usize) {
+        if !self.engines.iter().any(|e| e == engine) {
+            self.engines.push(engine.to_string());
+        }
+        self.engine_requests += requests;
+    }
+
+    /// Record the requests made to a people search site
+    pub fn contacted_site(&mut self, site: &str, requests: usize) {
+        if !self.sites.iter().any(|s| s == site) {
+            self.sites.push(site.to_string());
+        }
+        self.site_requests += requests;
+    }
+
+    /// SHA-256 over the entry's JSON with the `hash` field left out
+    pub fn compute_hash(&self) -> Result<String> {
+        let mut unhashed = self.clone();
+        unhashed.hash.clear();
+        let json = serde_json::to_string(&unhashed)?;
+        Ok(hex::encode(Sha256::digest(json.as_bytes())))
+    }
+}
+
+/// Audit entry for the current run, written when the run ends
+#[derive(Debug, Default)]
+pub struct RunRecord {
+    path: Option<PathBuf>,
+    pub entry: AuditEntry,
+}
+
+impl RunRecord {
+    /// Start auditing a run; nothing is written unless this is called
+    pub fn start(&mut self, path: PathBuf, entry: AuditEntry) {
+        self.path = Some(path);
+        self.entry = entry;
+    }
+
+    /// Append the run's entry. An outcome set during the run (blocked,
+    /// refused) is kept; otherwise it is taken from the run's result.
+    pub fn finish(mut self, result: &Result<()>) -> Result<()> {
+        let Some(path) = self.path.take() else {
+            return Ok(());
+        };
+        if self.entry.outcome.is_empty() {
+            self.entry.outcome = match result {
+                Ok(()) => "completed".to_string(),
+                Err(e) => format!("error: {}", e),
+            };
+        }
+        append(&path, self.entry)?;
+        Ok(())
+    }
+}
+
+/// How a number is written to the log: as-is, or when a key is given, an
+/// HMAC-SHA256 keyed with the install's secret. A plain hash of a 10-digit
+/// number could be brute-forced; the keyed one is only comparable between
+/// entries written by the same install.
+pub fn number_field(digits: &str, key: Option<&[u8]>) -> String {
+    match key {
+        Some(key) => format!("hmac-sha256:{}", keyed_hash(key, digits)),
+        None => digits.to_string(),
+    }
+}
+
+/// Hex HMAC-SHA256 of a number under the install's key
+pub fn keyed_hash(key: &[u8], digits: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(digits.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Location of the per-install hashing key: `~/.config/telespotter/audit.key`,
+/// or beside the audit log when HOME is unset
+pub fn key_path(log: &Path) -> PathBuf {
+    config_dir()
+        .or_else(|| log.parent().map(Path::to_path_buf))
+        .unwrap_or_default()
+        .join("audit.key")
+}
+
+/// Read the install's hashing key, generating and saving one on first use
+pub fn load_key(path: &Path) -> Result<Vec<u8>> {
+    if path.exists() {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read audit key {}", path.display()))?;
+        return hex::decode(contents.trim()).with_context(|| format!("Invalid audit key {}", path.display()));
+    }
+
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+    let key = rand::random::<[u8; KEY_LEN]>().to_vec();
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .with_context(|| format!("Failed to create audit key {}", path.display()))?;
+    writeln!(file, "{}", hex::encode(&key))?;
+    Ok(key)
+}
+
+/// Audit log location: the configured path or `~/.config/telespotter/audit.jsonl`
+pub fn log_path(configured: Option<&str>) -> Result<PathBuf> {
+    configured
+        .map(PathBuf::from)
+        .or_else(|| config_dir().map(|dir| dir.join("audit.jsonl")))
+        .ok_or_else(|| anyhow!("Cannot locate audit log: set \"audit_log\" in config or HOME"))
+}
+
+/// Chain an entry onto the end of the log and append it. The log is
+/// only ever opened for appending.
+pub fn append(path: &Path, mut entry: AuditEntry) -> Result<AuditEntry> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+
+    match last_entry(path)? {
+        Some(last) => {
+            entry.seq = last.seq + 1;
+            entry.prev_hash = last.hash;
+        }
+        None => {
+            entry.seq = 1;
+            entry.prev_hash = GENESIS_HASH.to_string();
+        }
+    }
+    entry.hash = entry.compute_hash()?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open audit log {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+    Ok(entry)
+}
+
+fn last_entry(path: &Path) -> Result<Option<AuditEntry>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read audit log {}", path.display()))?;
+    match contents.lines().rev().find(|line| !line.trim().is_empty()) {
+        Some(line) => Ok(Some(serde_json::from_str(line).with_context(|| {
+            format!("Last audit log entry in {} is not valid", path.display())
+        })?)),
+        None => Ok(None),
+    }
+}
+
+/// Every readable entry in the log; a missing log has no entries
+pub fn read_entries(path: &Path) -> Result<Vec<AuditEntry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read audit log {}", path.display()))?;
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Result of checking an audit log's hash chain
+#[derive(Debug, Default)]
+pub struct VerifyReport {
+    pub entries: usize,
+    /// Hash of the last entry; record it elsewhere to detect truncation
+    pub head: Option<String>,
+    pub problems: Vec<String>,
+}
+
+impl VerifyReport {
+    pub fn is_valid(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Walk the log checking sequence numbers, entry hashes and chain links
+pub fn verify(path: &Path) -> Result<VerifyReport> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read audit log {}", path.display()))?;
+    Ok(verify_lines(&contents))
+}
+
+fn verify_lines(contents: &str) -> VerifyReport {
+    let mut report = VerifyReport::default();
+    let mut prev_hash = GENESIS_HASH.to_string();
+    let mut expected_seq = 1;
+
+    for (i, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line_no = i + 1;
+        report.entries += 1;
+
+        let entry: AuditEntry = match serde_json::from_str(line) {
+            Ok(entry) => entry,
+            Err(e) => {
+                report.problems.push(format!("line {}: unreadable entry ({})", line_no, e));
+                continue;
+            }
+        };
+
+        if entry.seq != expected_seq {
+            report.problems.push(format!(
+                "line {}: sequence {} where {} was expected (entries missing or reordered)",
+                line_no, entry.seq, expected_seq
+            ));
+        }
+        if entry.prev_hash != prev_hash {
+            report.problems.push(format!(
+                "line {}: previous-hash link broken (an earlier entry was changed or removed)",
+                line_no
+            ));
+        }
+        match entry.compute_hash() {
+            Ok(hash) if hash == entry.hash => {}
+            _ => report.problems.push(format!("line {}: entry contents were modified", line_no)),
+        }
+
+        expected_seq = entry.seq + 1;
+        prev_hash = entry.hash.clone();
+        report.head = Some(entry.hash);
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_log(name: &str, count: usize) -> (PathBuf, String) {
+        let path = std::env::temp_dir().join(format!("telespotter_audit_{}_{}.jsonl", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        for i in 0..count {
+            let mut entry = AuditEntry::new("lookup", &format!("555555121{}", i));
+            entry.contacted_engine("google", 1);
+            entry.outcome = "completed".to_string();
+            append(&path, entry).unwrap();
+        }
+        let contents = fs::read_to_string(&path).unwrap();
+        (path, contents)
+    }
+
+    #[test]
+    fn test_number_hash_is_keyed() {
+        let path = std::env::temp_dir().join(format!("telespotter_audit_{}.key", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let key = load_key(&path).unwrap();
+        assert_eq!(load_key(&path).unwrap(), key);
+
+        let hashed = number_field("5555551212", Some(&key));
+        assert!(hashed.starts_with("hmac-sha256:"));
+        assert_eq!(hashed, number_field("5555551212", Some(&key)));
+        assert_ne!(hashed, number_field("5555551212", Some(b"another install")));
+        assert!(!hashed.contains(&hex::encode(Sha256::digest(b"5555551212"))));
+        assert_eq!(number_field("5555551212", None), "5555551212");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_chain_verifies() {
+        let (path, contents) = write_log("valid", 3);
+        let report = verify_lines(&contents);
+        assert_eq!(report.entries, 3);
+        assert!(report.is_valid(), "{:?}", report.problems);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_detects_edit_and_deletion() {
+        let (path, contents) = write_log("tampered", 3);
+
+        let edited = contents.replacen("5555551211", "5555559999", 1);
+        let report = verify_lines(&edited);
+        assert_eq!(report.problems.len(), 1);
+        assert!(report.problems[0].contains("line 2: entry contents"));
+
+        let lines: Vec<&str> = contents.lines().collect();
+        let deleted = format!("{}\n{}\n", lines[0], lines[2]);
+        let report = verify_lines(&deleted);
+        assert!(!report.is_valid());
+        assert!(report.problems.iter().all(|p| p.starts_with("line 2:")));
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -11,9 +11,8 @@ pub async fn search(query: &str, num_results: usize) -> Result<Vec<SearchResult>
 /// Search Bing with custom configuration
 pub async fn search_with_config(query: &str, num_results: usize, config: &SearchConfig) -> Result<Vec<SearchResult>> {
     let client = create_client_from_config(config);
-    // Wrap query in quotes for exact phrase matching
-    let quoted_query = format!("\"{}\"", query);
-    let encoded_query = urlencoding::encode(&quoted_query);
+    // Query is sent as-is; quoting comes from the query template
+    let encoded_query = urlencoding::encode(query);
     let url = format!(
         "https://www.bing.com/search?q={}&count={}",
         encoded_query, num_results
@@ -33,6 +32,7 @@ pub async fn search_with_config(query: &str, num_results: usize, config: &Search
     // Selector for Bing search results
     let result_selector = Selector::parse("li.b_algo").unwrap();
     let title_selector = Selector::parse("h2").unwrap();
+    let link_selector = Selector::parse("h2 a[href]").unwrap();
     let snippet_selector = Selector::parse("p").unwrap();
 
     for element in document.select(&result_selector) {
@@ -42,6 +42,12 @@ pub async fn search_with_config(query: &str, num_results: usize, config: &Search
             .map(|e| e.text().collect::<String>())
             .unwrap_or_default();
 
+        let url = element
+            .select(&link_selector)
+            .next()
+            .and_then(|e| e.value().attr("href"))
+            .unwrap_or_default();
+
         let snippet = element
             .select(&snippet_selector)
             .next()
@@ -53,7 +59,7 @@ pub async fn search_with_config(query: &str, num_results: usize, config: &Search
                 title,
                 snippet,
                 "Bing".to_string(),
-            ));
+            ).with_url(url));
         }
     }
 
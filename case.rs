@@ -0,0 +1,192 @@
+use crate::audit;
+use crate::crypt::{self, Encryption};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Root for case folders when the config doesn't set `case_root`
+pub const DEFAULT_CASE_ROOT: &str = "cases";
+
+/// Folder used for runs that aren't attached to a case
+pub const NO_CASE_DIR: &str = "no-case";
+
+/// Evidence manifest file written next to saved results
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Case management details carried through a run and its outputs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CaseInfo {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub case_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub investigator: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl CaseInfo {
+    /// Command-line values win over config values; blank values are dropped
+    pub fn resolve(
+        case_id: Option<&str>,
+        investigator: Option<&str>,
+        note: Option<&str>,
+        config: &CaseInfo,
+    ) -> Self {
+        let pick = |arg: Option<&str>, configured: &Option<String>| {
+            arg.map(str::to_string)
+                .or_else(|| configured.clone())
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        CaseInfo {
+            case_id: pick(case_id, &config.case_id),
+            investigator: pick(investigator, &config.investigator),
+            note: pick(note, &config.note),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.case_id.is_none() && self.investigator.is_none() && self.note.is_none()
+    }
+
+    /// Case id made safe for file and folder names
+    pub fn file_label(&self) -> Option<String> {
+        self.case_id.as_deref().map(sanitize)
+    }
+}
+
+/// Replace anything but letters, digits, '-', '_' and '.' with '_'
+pub fn sanitize(value: &str) -> String {
+    let cleaned: String = value
+        .chars()
+        .map(|c| if c.is_alphanumeric() || "-_.".contains(c) { c } else { '_' })
+        .collect();
+    cleaned.trim_matches('.').to_string()
+}
+
+/// Folder holding a case's runs: `<root>/<case>`
+pub fn case_dir(root: &Path, case: &CaseInfo) -> PathBuf {
+    root.join(case.file_label().unwrap_or_else(|| NO_CASE_DIR.to_string()))
+}
+
+/// Hex characters of the keyed hash used in place of the number
+const NUMBER_LABEL_LEN: usize = 16;
+
+/// How the number appears in folder and file names: the digits, or with a
+/// key (redacted or encrypted output), a keyed hash that keeps one number's
+/// runs together without revealing it
+pub fn number_label(digits: &str, key: Option<&[u8]>) -> String {
+    match key {
+        Some(key) => audit::keyed_hash(key, digits)[..NUMBER_LABEL_LEN].to_string(),
+        None => digits.to_string(),
+    }
+}
+
+/// Folder for one run: `<root>/<case>/<number label>/<timestamp>`
+pub fn run_dir(root: &Path, case: &CaseInfo, number_label: &str, started: &DateTime<Utc>) -> PathBuf {
+    case_dir(root, case)
+        .join(number_label)
+        .join(started.format("%Y%m%dT%H%M%SZ").to_string())
+}
+
+/// Results file name, prefixed with the case id when there is one
+pub fn results_file_name(case: &CaseInfo, number_label: &str, ext: &str) -> String {
+    match case.file_label() {
+        Some(label) => format!("telespotter_{}_{}.{}", label, number_label, ext),
+        None => format!("telespotter_results_{}.{}", number_label, ext),
+    }
+}
+
+/// A file covered by the evidence manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestFile {
+    pub name: String,
+    pub bytes: u64,
+    pub sha256: String,
+}
+
+/// Evidence manifest: what was saved for a run, with content hashes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub version: String,
+    pub created: String,
+    #[serde(flatten)]
+    pub case: CaseInfo,
+    pub phone_number: String,
+    pub files: Vec<ManifestFile>,
+}
+
+impl Manifest {
+    pub fn new(case: &CaseInfo, phone_number: &str) -> Self {
+        Manifest {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            created: Utc::now().to_rfc3339(),
+            case: case.clone(),
+            phone_number: phone_number.to_string(),
+            files: Vec::new(),
+        }
+    }
+
+    /// Hash a saved file and add it to the manifest
+    pub fn add_file(&mut self, path: &Path) -> Result<()> {
+        let contents = fs::read(path)
+            .with_context(|| format!("Failed to read {} for manifest", path.display()))?;
+        self.files.push(ManifestFile {
+            name: path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            bytes: contents.len() as u64,
+            sha256: hex::encode(Sha256::digest(&contents)),
+        });
+        Ok(())
+    }
+
+    /// Write the manifest, encrypted like the files it lists
+    pub fn write(&self, path: &Path, encryption: Option<&Encryption>) -> Result<PathBuf> {
+        crypt::write_file(path, serde_json::to_string_pretty(self)?.as_bytes(), encryption)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_resolve_and_layout() {
+        let configured = CaseInfo {
+            case_id: Some("from-config".to_string()),
+            investigator: Some("jdoe".to_string()),
+            note: None,
+        };
+        let case = CaseInfo::resolve(Some("2024/017 Smith"), None, Some("  "), &configured);
+        assert_eq!(case.case_id.as_deref(), Some("2024/017 Smith"));
+        assert_eq!(case.investigator.as_deref(), Some("jdoe"));
+        assert!(case.note.is_none());
+
+        let started = Utc.with_ymd_and_hms(2024, 3, 5, 14, 7, 9).unwrap();
+        assert_eq!(
+            run_dir(Path::new("cases"), &case, "5555551212", &started),
+            PathBuf::from("cases/2024_017_Smith/5555551212/20240305T140709Z")
+        );
+        assert_eq!(
+            results_file_name(&case, "5555551212", "json"),
+            "telespotter_2024_017_Smith_5555551212.json"
+        );
+        assert_eq!(
+            run_dir(Path::new("cases"), &CaseInfo::default(), "5555551212", &started),
+            PathBuf::from("cases/no-case/5555551212/20240305T140709Z")
+        );
+
+        let hidden = number_label("5555551212", Some(b"install key"));
+        assert_eq!(hidden.len(), NUMBER_LABEL_LEN);
+        assert_eq!(hidden, number_label("5555551212", Some(b"install key")));
+        assert_ne!(hidden, number_label("5555551213", Some(b"install key")));
+        assert!(!results_file_name(&case, &hidden, "json").contains("5555551212"));
+        assert_eq!(number_label("5555551212", None), "5555551212");
+    }
+}
@@ -0,0 +1,287 @@
+use crate::entity::Entity;
+use crate::exclusion::Section;
+use crate::names::{canonical, is_initial, is_particle, is_suffix, same_given_name, same_surname};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Analyst decisions that override automatic clustering. Names are matched
+/// in canonical form, so "SMITH, JOHN" and "John Smith" are the same entry.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Review {
+    /// Groups of names that are one person
+    pub merge: Vec<Vec<String>>,
+    /// Groups of names that must end up in different clusters
+    pub split: Vec<Vec<String>>,
+    /// Entities that are noise, to leave out of this run and to promote to
+    /// an exclusion list with `telespotter exclude`
+    pub exclude: ReviewExclusions,
+}
+
+/// Entities marked as noise in a review file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReviewExclusions {
+    pub names: Vec<String>,
+    pub usernames: Vec<String>,
+    pub emails: Vec<String>,
+}
+
+impl Review {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read review file {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid review file {}", path.display()))
+    }
+
+    /// The `exclude` entries as exclusion list entries
+    pub fn exclusions(&self) -> Vec<(Section, String)> {
+        let section = |section: Section, values: &[String]| {
+            values.iter().map(move |value| (section, value.clone())).collect::<Vec<_>>()
+        };
+        [
+            section(Section::Words, &self.exclude.names),
+            section(Section::Usernames, &self.exclude.usernames),
+            section(Section::Emails, &self.exclude.emails),
+        ]
+        .concat()
+    }
+
+    fn in_same_group(groups: &[Vec<String>], a: &str, b: &str) -> bool {
+        groups.iter().any(|group| {
+            let group: Vec<String> = group.iter().map(|name| canonical(name)).collect();
+            group.iter().any(|n| n == a) && group.iter().any(|n| n == b)
+        })
+    }
+
+    fn must_merge(&self, a: &str, b: &str) -> bool {
+        Self::in_same_group(&self.merge, a, b)
+    }
+
+    fn must_split(&self, a: &str, b: &str) -> bool {
+        Self::in_same_group(&self.split, a, b)
+    }
+}
+
+/// Names believed to be one person, with their evidence combined
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NameCluster {
+    /// Display name: the best-supported member written in full
+    pub label: String,
+    /// Every spelling in the cluster, best supported first
+    pub members: Vec<String>,
+    /// All members' sources merged, with confidence recomputed
+    pub evidence: Entity,
+}
+
+/// A canonical name split into the parts compared when clustering
+struct NameParts<'a> {
+    given: &'a str,
+    middle: Option<&'a str>,
+    surname: &'a str,
+    suffix: Option<&'a str>,
+}
+
+impl<'a> NameParts<'a> {
+    fn parse(name: &'a str) -> Option<Self> {
+        let words: Vec<&str> = name.split_whitespace().collect();
+        let core = words.iter().take_while(|w| !is_suffix(w)).count();
+        if core < 2 {
+            return None;
+        }
+        Some(NameParts {
+            given: words[0],
+            middle: words[1..core - 1].iter().find(|w| !is_particle(w)).copied(),
+            surname: words[core - 1],
+            suffix: words.get(core).copied(),
+        })
+    }
+
+    /// Same surname, compatible given and middle names, no clashing suffix
+    fn same_person(&self, other: &NameParts) -> bool {
+        same_surname(self.surname, other.surname)
+            && same_given_name(self.given, other.given)
+            && match (self.middle, other.middle) {
+                (Some(a), Some(b)) => same_given_name(a, b),
+                _ => true,
+            }
+            && match (self.suffix, other.suffix) {
+                (Some(a), Some(b)) => a == b,
+                _ => true,
+            }
+    }
+}
+
+/// Group name entities (ranked, best first) into likely identities using
+/// nicknames, initials and near spellings, then the review's merges and
+/// splits. Clusters only form when every pair of members is compatible, and
+/// an initial that fits several different people ("J. Smith" with both
+/// "John Smith" and "Jane Smith") is left on its own.
+pub fn cluster_names(names: &[Entity], review: &Review) -> Vec<NameCluster> {
+    let parts: Vec<Option<NameParts>> = names.iter().map(|e| NameParts::parse(&e.value)).collect();
+    let related = |i: usize, j: usize| match (&parts[i], &parts[j]) {
+        (Some(a), Some(b)) => a.same_person(b),
+        _ => false,
+    };
+    let value = |i: usize| names[i].value.as_str();
+
+    // An initial is ambiguous when it fits people who don't fit each other
+    let ambiguous: Vec<bool> = (0..names.len())
+        .map(|i| {
+            let Some(part) = &parts[i] else {
+                return false;
+            };
+            if !is_initial(part.given) {
+                return false;
+            }
+            let partners: Vec<usize> = (0..names.len())
+                .filter(|&j| j != i && related(i, j))
+                .filter(|&j| parts[j].as_ref().is_some_and(|p| !is_initial(p.given)))
+                .collect();
+            partners
+                .iter()
+                .any(|&a| partners.iter().any(|&b| !related(a, b)))
+        })
+        .collect();
+
+    let mut groups: Vec<Vec<usize>> = (0..names.len()).map(|i| vec![i]).collect();
+    let group_of = |groups: &[Vec<usize>], i: usize| groups.iter().position(|g| g.contains(&i)).unwrap();
+
+    // Analyst merges first, then automatic links in rank order
+    let mut links: Vec<(usize, usize, bool)> = Vec::new();
+    for i in 0..names.len() {
+        for j in i + 1..names.len() {
+            if review.must_merge(value(i), value(j)) {
+                links.insert(0, (i, j, true));
+            } else if related(i, j) && !ambiguous[i] && !ambiguous[j] {
+                links.push((i, j, false));
+            }
+        }
+    }
+    for (i, j, forced) in links {
+        let (gi, gj) = (group_of(&groups, i), group_of(&groups, j));
+        if gi == gj {
+            continue;
+        }
+        let compatible = groups[gi].iter().all(|&a| {
+            groups[gj].iter().all(|&b| {
+                let merged = review.must_merge(value(a), value(b));
+                !review.must_split(value(a), value(b)) && (forced || merged || related(a, b))
+            })
+        });
+        if compatible {
+            let moved = std::mem::take(&mut groups[gj]);
+            groups[gi].extend(moved);
+        }
+    }
+
+    let mut clusters: Vec<NameCluster> = groups
+        .into_iter()
+        .filter(|group| !group.is_empty())
+        .map(|mut group| {
+            group.sort();
+            let label = group
+                .iter()
+                .find(|&&i| parts[i].as_ref().is_some_and(|p| !is_initial(p.given)))
+                .map_or(value(group[0]), |&i| value(i))
+                .to_string();
+            let mut evidence = Entity::new(&label);
+            for &i in &group {
+                for source in &names[i].sources {
+                    evidence.add_source(source.clone());
+                }
+                if let Some(likelihood) = names[i].likelihood {
+                    evidence.likelihood = Some(evidence.likelihood.map_or(likelihood, |l| l.max(likelihood)));
+                }
+            }
+            NameCluster {
+                label,
+                members: group.iter().map(|&i| value(i).to_string()).collect(),
+                evidence,
+            }
+        })
+        .collect();
+    clusters.sort_by(|a, b| {
+        b.evidence
+            .confidence
+            .total_cmp(&a.evidence.confidence)
+            .then(b.evidence.pages.cmp(&a.evidence.pages))
+            .then_with(|| a.label.cmp(&b.label))
+    });
+    clusters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{EntitySource, SourceKind};
+
+    fn entity(name: &str, page: &str) -> Entity {
+        let mut entity = Entity::new(name);
+        entity.add_source(EntitySource {
+            mentions: 1,
+            ..EntitySource::new(page, page, vec!["Google".to_string()], SourceKind::Web, true)
+        });
+        entity
+    }
+
+    fn members(clusters: &[NameCluster]) -> Vec<Vec<&str>> {
+        let mut members: Vec<Vec<&str>> = clusters
+            .iter()
+            .map(|c| c.members.iter().map(String::as_str).collect())
+            .collect();
+        members.sort();
+        members
+    }
+
+    #[test]
+    fn test_clusters_variants_and_keeps_ambiguous_initials_apart() {
+        let names = vec![
+            entity("Robert Smith", "a"),
+            entity("Bob Smith", "b"),
+            entity("R. Smith", "c"),
+            entity("John Jones", "d"),
+            entity("Jane Jones", "e"),
+            entity("J. Jones", "f"),
+        ];
+        let clusters = cluster_names(&names, &Review::default());
+        assert_eq!(
+            members(&clusters),
+            vec![
+                vec!["J. Jones"],
+                vec!["Jane Jones"],
+                vec!["John Jones"],
+                vec!["Robert Smith", "Bob Smith", "R. Smith"],
+            ]
+        );
+        let robert = clusters.iter().find(|c| c.label == "Robert Smith").unwrap();
+        assert_eq!(robert.evidence.pages, 3);
+    }
+
+    #[test]
+    fn test_review_merges_and_splits() {
+        let names = vec![
+            entity("Robert Smith", "a"),
+            entity("Bob Smith", "b"),
+            entity("John Jones", "d"),
+            entity("J. Jones", "f"),
+        ];
+        let review = Review {
+            merge: vec![vec!["JONES, JOHN".to_string(), "Robert Smith".to_string()]],
+            split: vec![vec!["Bob Smith".to_string(), "Robert Smith".to_string()]],
+            ..Default::default()
+        };
+        let clusters = cluster_names(&names, &review);
+        assert_eq!(
+            members(&clusters),
+            vec![
+                vec!["Bob Smith"],
+                vec!["J. Jones"],
+                vec!["Robert Smith", "John Jones"],
+            ]
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use crate::case::CaseInfo;
+use crate::parser::EntityKind;
+use crate::retention::RetentionPolicy;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Environment variable that overrides the config file location
+pub const CONFIG_ENV_VAR: &str = "TELESPOTTER_CONFIG";
+
+/// User configuration loaded from a JSON file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Custom query templates by name (e.g. "reviews": "\"{fmt}\" reviews")
+    pub query_templates: HashMap<String, String>,
+    /// Extra phone-directory domains to flag as directory spam
+    pub spam_domains: Vec<String>,
+    /// Purposes accepted by `--purpose` (defaults to `purpose::DEFAULT_PURPOSES`)
+    pub permissible_purposes: Vec<String>,
+    /// Default operator identifier when `--operator` isn't given
+    pub operator: Option<String>,
+    /// Do-not-search list path (default: ~/.config/telespotter/protected.txt)
+    pub protected_numbers: Option<String>,
+    /// Audit log path (default: ~/.config/telespotter/audit.jsonl)
+    pub audit_log: Option<String>,
+    /// Write a keyed hash of the number to the audit log instead of the number
+    pub audit_hash_numbers: bool,
+    /// Default case id, investigator and note
+    #[serde(flatten)]
+    pub case: CaseInfo,
+    /// Root folder for saved case files (default: ./cases)
+    pub case_root: Option<String>,
+    /// age public keys every saved file is encrypted to
+    pub encryption_recipients: Vec<String>,
+    /// age identity file used by `decrypt`/`view`
+    pub identity_file: Option<String>,
+    /// How long saved data is kept before `purge` deletes it
+    pub retention: RetentionPolicy,
+    /// Entity types to extract when `--collect` isn't given (empty means all)
+    pub collect: Vec<EntityKind>,
+    /// User exclusion list path (default: ~/.config/telespotter/exclusions.txt)
+    pub exclusions: Option<String>,
+}
+
+impl Config {
+    /// Load configuration from an explicit path, the `TELESPOTTER_CONFIG`
+    /// environment variable, or `~/.config/telespotter/config.json`.
+    ///
+    /// A missing default config file is not an error; an explicitly
+    /// requested one is.
+    pub fn load(path: Option<&str>) -> Result<Self> {
+        let explicit = path
+            .map(PathBuf::from)
+            .or_else(|| env::var(CONFIG_ENV_VAR).ok().map(PathBuf::from));
+
+        let path = match explicit {
+            Some(path) => path,
+            None => match default_config_path() {
+                Some(path) if path.exists() => path,
+                _ => return Ok(Config::default()),
+            },
+        };
+
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid config file {}", path.display()))
+    }
+}
+
+/// Directory holding per-user telespotter files (`~/.config/telespotter`)
+pub fn config_dir() -> Option<PathBuf> {
+    env::var("HOME")
+        .ok()
+        .map(|home| PathBuf::from(home).join(".config").join("telespotter"))
+}
+
+fn default_config_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("config.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_partial_config() {
+        let config: Config =
+            serde_json::from_str(r#"{"query_templates": {"reviews": "\"{fmt}\" reviews"}}"#).unwrap();
+        assert_eq!(
+            config.query_templates.get("reviews").map(String::as_str),
+            Some("\"{fmt}\" reviews")
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use age::secrecy::SecretString;
+use age::x25519;
+use anyhow::{anyhow, bail, Context, Result};
+use std::fs;
+use std::io::{Read, Write};
+use std::iter;
+use std::path::{Path, PathBuf};
+
+/// Extension appended to encrypted files
+pub const ENCRYPTED_EXTENSION: &str = "age";
+
+/// Environment variable holding the passphrase, for non-interactive runs
+pub const PASSPHRASE_ENV_VAR: &str = "TELESPOTTER_PASSPHRASE";
+
+/// First bytes of every (binary) age file
+const AGE_MAGIC: &[u8] = b"age-encryption.org/v1";
+
+/// How saved files are encrypted
+pub enum Encryption {
+    /// Anyone with the passphrase can decrypt
+    Passphrase(SecretString),
+    /// Only holders of the matching age identities can decrypt
+    Recipients(Vec<x25519::Recipient>),
+}
+
+impl Encryption {
+    /// Parse age public keys (`age1...`)
+    pub fn recipients(keys: &[String]) -> Result<Self> {
+        let recipients = keys
+            .iter()
+            .map(|key| {
+                key.trim()
+                    .parse::<x25519::Recipient>()
+                    .map_err(|e| anyhow!("Invalid recipient '{}': {}", key, e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if recipients.is_empty() {
+            bail!("No encryption recipients given");
+        }
+        Ok(Encryption::Recipients(recipients))
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let encryptor = match self {
+            Encryption::Passphrase(passphrase) => {
+                age::Encryptor::with_user_passphrase(passphrase.clone())
+            }
+            Encryption::Recipients(recipients) => age::Encryptor::with_recipients(
+                recipients.iter().map(|r| r as &dyn age::Recipient),
+            )?,
+        };
+
+        let mut ciphertext = Vec::with_capacity(plaintext.len() + 256);
+        let mut writer = encryptor.wrap_output(&mut ciphertext)?;
+        writer.write_all(plaintext)?;
+        writer.finish()?;
+        Ok(ciphertext)
+    }
+}
+
+/// Passphrase from `TELESPOTTER_PASSPHRASE`, or prompted for without echo
+pub fn read_passphrase(prompt: &str) -> Result<SecretString> {
+    let passphrase = match std::env::var(PASSPHRASE_ENV_VAR) {
+        Ok(value) => value,
+        Err(_) => rpassword::prompt_password(prompt)?,
+    };
+    if passphrase.is_empty() {
+        bail!("Empty passphrase");
+    }
+    Ok(SecretString::from(passphrase))
+}
+
+/// Write a file, encrypting it to `<path>.age` when encryption is on.
+/// Returns the path actually written.
+pub fn write_file(path: &Path, contents: &[u8], encryption: Option<&Encryption>) -> Result<PathBuf> {
+    let (path, bytes) = match encryption {
+        Some(encryption) => (encrypted_path(path), encryption.encrypt(contents)?),
+        None => (path.to_path_buf(), contents.to_vec()),
+    };
+    fs::write(&path, bytes).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(ENCRYPTED_EXTENSION);
+    PathBuf::from(name)
+}
+
+/// Path a decrypted copy is written to: `<file>.age` -> `<file>`
+pub fn decrypted_path(path: &Path) -> PathBuf {
+    match path.extension() {
+        Some(ext) if ext == ENCRYPTED_EXTENSION => path.with_extension(""),
+        _ => {
+            let mut name = path.as_os_str().to_owned();
+            name.push(".decrypted");
+            PathBuf::from(name)
+        }
+    }
+}
+
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(AGE_MAGIC)
+}
+
+/// Load x25519 identities (`AGE-SECRET-KEY-1...` lines) from an age key file
+pub fn load_identities(path: &Path) -> Result<Vec<x25519::Identity>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read identity file {}", path.display()))?;
+    let identities = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("AGE-SECRET-KEY-"))
+        .map(|line| {
+            line.parse::<x25519::Identity>()
+                .map_err(|e| anyhow!("Invalid identity in {}: {}", path.display(), e))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if identities.is_empty() {
+        bail!("No identities found in {}", path.display());
+    }
+    Ok(identities)
+}
+
+/// Decrypt a file's contents. Passphrase-encrypted files ask for the
+/// passphrase; key-encrypted files need `identities`. Unencrypted data is
+/// returned as-is.
+pub fn decrypt(data: &[u8], identities: &[x25519::Identity]) -> Result<Vec<u8>> {
+    if !is_encrypted(data) {
+        return Ok(data.to_vec());
+    }
+
+    let decryptor = age::Decryptor::new_buffered(data)?;
+    let mut reader = if decryptor.is_scrypt() {
+        let passphrase = read_passphrase("Passphrase: ")?;
+        let identity = age::scrypt::Identity::new(passphrase);
+        decryptor.decrypt(iter::once(&identity as &dyn age::Identity))?
+    } else {
+        if identities.is_empty() {
+            bail!("File is encrypted to a public key; pass --identity <KEY FILE>");
+        }
+        decryptor.decrypt(identities.iter().map(|i| i as &dyn age::Identity))?
+    };
+
+    let mut plaintext = Vec::new();
+    reader.read_to_end(&mut plaintext)?;
+    Ok(plaintext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recipient_round_trip() {
+        let identity = x25519::Identity::generate();
+        let encryption = Encryption::recipients(&[identity.to_public().to_string()]).unwrap();
+
+        let ciphertext = encryption.encrypt(b"John Smith, Philadelphia, PA").unwrap();
+        assert!(is_encrypted(&ciphertext));
+        assert_eq!(decrypt(&ciphertext, &[identity]).unwrap(), b"John Smith, Philadelphia, PA");
+
+        assert!(decrypt(&ciphertext, &[x25519::Identity::generate()]).is_err());
+        assert!(Encryption::recipients(&["not-a-key".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_paths() {
+        assert_eq!(encrypted_path(Path::new("a/r.json")), PathBuf::from("a/r.json.age"));
+        assert_eq!(decrypted_path(Path::new("a/r.json.age")), PathBuf::from("a/r.json"));
+        assert_eq!(decrypted_path(Path::new("a/r.bin")), PathBuf::from("a/r.bin.decrypted"));
+    }
+}
@@ -0,0 +1,198 @@
+use crate::query::MatchMode;
+use crate::search::SearchResult;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Query parameters that only track clicks and never change the page
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "msclkid", "yclid", "ref", "ref_src", "ref_url", "source", "si", "igshid",
+];
+
+/// One engine/query/rank at which a page was found
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Hit {
+    pub engine: String,
+    pub query: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub format: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub template: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_mode: Option<MatchMode>,
+    pub rank: usize,
+}
+
+/// A page merged across every engine, format and template that returned it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergedResult {
+    /// Canonical URL, or "text:<normalized title+snippet>" when there is no URL
+    pub key: String,
+    pub result: SearchResult,
+    pub hits: Vec<Hit>,
+}
+
+impl MergedResult {
+    /// Distinct engines/sites that returned this page
+    pub fn engines(&self) -> Vec<String> {
+        let mut engines: Vec<String> = Vec::new();
+        for hit in &self.hits {
+            if !engines.contains(&hit.engine) {
+                engines.push(hit.engine.clone());
+            }
+        }
+        engines
+    }
+}
+
+/// Canonical form of a URL used to recognise the same page:
+/// scheme, "www."/"m." prefixes, fragments, tracking parameters and
+/// trailing slashes are ignored, and the remaining parameters are sorted.
+pub fn canonical_url(url: &str) -> Option<String> {
+    let parsed = reqwest::Url::parse(url).ok()?;
+    let host = parsed.host_str()?.to_lowercase();
+    let host = host
+        .strip_prefix("www.")
+        .or_else(|| host.strip_prefix("m."))
+        .unwrap_or(&host)
+        .to_string();
+
+    let mut params: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(key, _)| {
+            let key = key.to_lowercase();
+            !key.starts_with("utm_") && !TRACKING_PARAMS.contains(&key.as_str())
+        })
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    params.sort();
+
+    let path = parsed.path().trim_end_matches('/');
+    let mut canonical = format!("{}{}", host, path);
+    if !params.is_empty() {
+        let query: Vec<String> = params.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        canonical.push('?');
+        canonical.push_str(&query.join("&"));
+    }
+
+    Some(canonical)
+}
+
+/// Lowercased title+snippet with punctuation and extra whitespace removed
+fn normalized_text(result: &SearchResult) -> String {
+    format!("{} {}", result.title, result.snippet)
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Key identifying the page behind a result
+pub fn result_key(result: &SearchResult) -> String {
+    canonical_url(&result.url).unwrap_or_else(|| format!("text:{}", normalized_text(result)))
+}
+
+/// Merge results from every query into one record per page.
+///
+/// Queries are processed in sorted order so the output is stable; the
+/// merged record keeps the longest snippet and is verified or flagged as
+/// spam if any of its copies was.
+pub fn merge_results(all_results: &HashMap<String, Vec<SearchResult>>) -> Vec<MergedResult> {
+    let mut queries: Vec<&String> = all_results.keys().collect();
+    queries.sort();
+
+    let mut merged: Vec<MergedResult> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for query in queries {
+        for result in &all_results[query] {
+            let key = result_key(result);
+            let hit = Hit {
+                engine: result.source.clone(),
+                query: query.clone(),
+                format: result.format.clone(),
+                template: result.template.clone(),
+                match_mode: result.match_mode,
+                rank: result.rank,
+            };
+
+            match index.get(&key) {
+                Some(&i) => {
+                    let existing = &mut merged[i];
+                    if result.snippet.len() > existing.result.snippet.len() {
+                        existing.result.snippet = result.snippet.clone();
+                    }
+                    existing.result.verified |= result.verified;
+                    if result.directory_spam && !existing.result.directory_spam {
+                        existing.result.directory_spam = true;
+                        existing.result.spam_reason = result.spam_reason.clone();
+                    }
+                    existing.hits.push(hit);
+                }
+                None => {
+                    index.insert(key.clone(), merged.len());
+                    merged.push(MergedResult {
+                        key,
+                        result: result.clone(),
+                        hits: vec![hit],
+                    });
+                }
+            }
+        }
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(source: &str, url: &str, snippet: &str) -> SearchResult {
+        SearchResult::new("John Smith".to_string(), snippet.to_string(), source.to_string()).with_url(url)
+    }
+
+    #[test]
+    fn test_canonical_url() {
+        assert_eq!(
+            canonical_url("https://www.Example.com/page/?utm_source=x&b=2&a=1#top").as_deref(),
+            Some("example.com/page?a=1&b=2")
+        );
+        assert_eq!(
+            canonical_url("http://m.example.com/page").as_deref(),
+            Some("example.com/page")
+        );
+        assert!(canonical_url("").is_none());
+    }
+
+    #[test]
+    fn test_merge_across_engines_and_formats() {
+        let mut all_results = HashMap::new();
+        all_results.insert(
+            "\"555-555-1212\"".to_string(),
+            vec![
+                result("Google", "https://www.example.com/page", "short"),
+                result("Bing", "https://example.com/page/", "a longer snippet"),
+            ],
+        );
+        all_results.insert(
+            "\"5555551212\"".to_string(),
+            vec![
+                result("DuckDuckGo", "http://example.com/page?utm_medium=x", "short"),
+                result("Google", "", "Different page"),
+            ],
+        );
+
+        let merged = merge_results(&all_results);
+        assert_eq!(merged.len(), 2);
+
+        let page = merged.iter().find(|m| m.key == "example.com/page").unwrap();
+        assert_eq!(page.hits.len(), 3);
+        assert_eq!(page.engines(), vec!["Google", "Bing", "DuckDuckGo"]);
+        assert_eq!(page.result.snippet, "a longer snippet");
+
+        assert!(merged.iter().any(|m| m.key == "text:john smith different page"));
+    }
+}
@@ -11,9 +11,8 @@ pub async fn search(query: &str, num_results: usize) -> Result<Vec<SearchResult>
 /// Search DuckDuckGo with custom configuration
 pub async fn search_with_config(query: &str, num_results: usize, config: &SearchConfig) -> Result<Vec<SearchResult>> {
     let client = create_client_from_config(config);
-    // Wrap query in quotes for exact phrase matching
-    let quoted_query = format!("\"{}\"", query);
-    let encoded_query = urlencoding::encode(&quoted_query);
+    // Query is sent as-is; quoting comes from the query template
+    let encoded_query = urlencoding::encode(query);
     let url = format!("https://html.duckduckgo.com/html/?q={}", encoded_query);
 
     let response = client.get(&url).send().await?;
@@ -39,6 +38,13 @@ pub async fn search_with_config(query: &str, num_results: usize, config: &Search
             .map(|e| e.text().collect::<String>())
             .unwrap_or_default();
 
+        let url = element
+            .select(&title_selector)
+            .next()
+            .and_then(|e| e.value().attr("href"))
+            .map(resolve_redirect)
+            .unwrap_or_default();
+
         let snippet = element
             .select(&snippet_selector)
             .next()
@@ -50,7 +56,7 @@ pub async fn search_with_config(query: &str, num_results: usize, config: &Search
                 title,
                 snippet,
                 "DuckDuckGo".to_string(),
-            ));
+            ).with_url(url));
         }
 
         if results.len() >= num_results {
@@ -60,3 +66,33 @@ pub async fn search_with_config(query: &str, num_results: usize, config: &Search
 
     Ok(results)
 }
+
+/// Unwrap DuckDuckGo's `//duckduckgo.com/l/?uddg=<target>` redirect links
+fn resolve_redirect(href: &str) -> String {
+    let absolute = if href.starts_with("//") {
+        format!("https:{}", href)
+    } else {
+        href.to_string()
+    };
+
+    reqwest::Url::parse(&absolute)
+        .ok()
+        .and_then(|url| {
+            url.query_pairs()
+                .find(|(key, _)| key == "uddg")
+                .map(|(_, target)| target.into_owned())
+        })
+        .unwrap_or(absolute)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_redirect() {
+        let href = "//duckduckgo.com/l/?uddg=https%3A%2F%2Fexample.org%2Fpage&rut=abc";
+        assert_eq!(resolve_redirect(href), "https://example.org/page");
+        assert_eq!(resolve_redirect("https://example.org/"), "https://example.org/");
+    }
+}
@@ -0,0 +1,410 @@
+use crate::names::NameMatch;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// Characters of context kept either side of an entity in an excerpt
+pub const EXCERPT_CONTEXT: usize = 60;
+
+/// Marker wrapped around the entity in an excerpt
+pub const HIGHLIGHT: &str = "**";
+
+/// Weights of the confidence factors; they sum to 1
+const COVERAGE_WEIGHT: f64 = 0.35;
+const SOURCE_TYPE_WEIGHT: f64 = 0.25;
+const VERIFIED_WEIGHT: f64 = 0.2;
+const AGREEMENT_WEIGHT: f64 = 0.2;
+
+/// What kind of result an entity came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SourceKind {
+    /// Structured record from a people search site
+    PeopleSearch,
+    /// Ordinary search engine result
+    Web,
+    /// Result flagged as phone-directory spam
+    Directory,
+}
+
+impl SourceKind {
+    /// How much a single source of this kind is trusted
+    fn weight(&self) -> f64 {
+        match self {
+            SourceKind::PeopleSearch => 1.0,
+            SourceKind::Web => 0.6,
+            SourceKind::Directory => 0.2,
+        }
+    }
+}
+
+/// A page or people search record an entity was extracted from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntitySource {
+    /// Page key (see `dedup::MergedResult::key`) or the record's URL
+    pub key: String,
+    pub url: String,
+    /// Engines, or the people search site, that returned it
+    pub engines: Vec<String>,
+    pub kind: SourceKind,
+    /// Whether the source mentions the target number
+    pub verified: bool,
+    /// Times the entity appears in this source
+    pub mentions: usize,
+    /// Surrounding text with the entity wrapped in `**`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub excerpt: String,
+}
+
+impl EntitySource {
+    pub fn new(key: &str, url: &str, engines: Vec<String>, kind: SourceKind, verified: bool) -> Self {
+        EntitySource {
+            key: key.to_string(),
+            url: url.to_string(),
+            engines,
+            kind,
+            verified,
+            mentions: 0,
+            excerpt: String::new(),
+        }
+    }
+}
+
+/// An extracted value together with the results it came from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entity {
+    pub value: String,
+    /// 0-1 score from source count, source type, verification and agreement
+    pub confidence: f64,
+    /// For names, how name-like the value is (see `names::likelihood`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub likelihood: Option<f64>,
+    /// Total mentions across all sources
+    pub mentions: usize,
+    /// Distinct pages and records mentioning it
+    pub pages: usize,
+    /// Distinct engines and sites behind those pages
+    pub engines: usize,
+    pub sources: Vec<EntitySource>,
+}
+
+impl Entity {
+    pub fn new(value: &str) -> Self {
+        Entity {
+            value: value.to_string(),
+            confidence: 0.0,
+            likelihood: None,
+            mentions: 0,
+            pages: 0,
+            engines: 0,
+            sources: Vec::new(),
+        }
+    }
+
+    /// Add a source, combining it with an earlier source for the same page
+    pub fn add_source(&mut self, source: EntitySource) {
+        match self.sources.iter_mut().find(|s| s.key == source.key) {
+            Some(existing) => {
+                existing.mentions += source.mentions;
+                if existing.excerpt.is_empty() {
+                    existing.excerpt = source.excerpt;
+                }
+                for engine in source.engines {
+                    if !existing.engines.contains(&engine) {
+                        existing.engines.push(engine);
+                    }
+                }
+            }
+            None => self.sources.push(source),
+        }
+        self.recount();
+    }
+
+    fn recount(&mut self) {
+        self.mentions = self.sources.iter().map(|s| s.mentions).sum();
+        self.pages = self.sources.len();
+        self.engines = self.engine_names().len();
+        self.confidence = self.score();
+    }
+
+    /// Weighted sum of four factors, each 0-1:
+    /// - coverage: distinct sources, 1 - 0.5^pages
+    /// - source type: the most trusted kind of source it came from
+    /// - verification: share of sources that mention the target number
+    /// - agreement: distinct engines/sites, boosted when web and people
+    ///   search results agree
+    fn score(&self) -> f64 {
+        if self.sources.is_empty() {
+            return 0.0;
+        }
+        let coverage = 1.0 - 0.5f64.powi(self.pages as i32);
+        let source_type = self
+            .sources
+            .iter()
+            .map(|s| s.kind.weight())
+            .fold(0.0, f64::max);
+        let verified = self.sources.iter().filter(|s| s.verified).count() as f64 / self.sources.len() as f64;
+        let mut agreement = 1.0 - 0.5f64.powi(self.engines.saturating_sub(1) as i32);
+        let has = |kind: SourceKind| self.sources.iter().any(|s| s.kind == kind);
+        if has(SourceKind::Web) && has(SourceKind::PeopleSearch) {
+            agreement = (agreement + 0.25).min(1.0);
+        }
+
+        let score = COVERAGE_WEIGHT * coverage
+            + SOURCE_TYPE_WEIGHT * source_type
+            + VERIFIED_WEIGHT * verified
+            + AGREEMENT_WEIGHT * agreement;
+        (score * 1000.0).round() / 1000.0
+    }
+
+    /// Distinct engines and sites, sorted
+    pub fn engine_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.sources.iter().flat_map(|s| s.engines.clone()).collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// "confidence 0.82, 3 mention(s) on 2 page(s), 2 source(s)"
+    pub fn summary(&self) -> String {
+        format!(
+            "confidence {:.2}, {} mention(s) on {} page(s), {} source(s)",
+            self.confidence, self.mentions, self.pages, self.engines
+        )
+    }
+}
+
+/// Collects entities by value while results are scanned one at a time
+#[derive(Debug, Default)]
+pub struct EntityTally {
+    entities: HashMap<String, Entity>,
+}
+
+impl EntityTally {
+    pub fn new() -> Self {
+        EntityTally::default()
+    }
+
+    /// Record the values extracted from one source; repeats count as extra
+    /// mentions. `texts` are searched in order for each value's excerpt.
+    pub fn add(&mut self, values: &[String], source: &EntitySource, texts: &[&str]) {
+        let mut counts: Vec<(&String, usize)> = Vec::new();
+        for value in values {
+            match counts.iter_mut().find(|(v, _)| *v == value) {
+                Some(entry) => entry.1 += 1,
+                None => counts.push((value, 1)),
+            }
+        }
+        for (value, mentions) in counts {
+            self.entities
+                .entry(value.clone())
+                .or_insert_with(|| Entity::new(value))
+                .add_source(EntitySource {
+                    mentions,
+                    excerpt: texts.iter().find_map(|text| excerpt(text, value)).unwrap_or_default(),
+                    ..source.clone()
+                });
+        }
+    }
+
+    /// Record normalized values paired with the text they were written as,
+    /// which is what the excerpts are found by
+    pub fn add_written(&mut self, found: &[(String, String)], source: &EntitySource, texts: &[&str]) {
+        for (value, written) in found {
+            self.entities
+                .entry(value.clone())
+                .or_insert_with(|| Entity::new(value))
+                .add_source(EntitySource {
+                    mentions: 1,
+                    excerpt: texts.iter().find_map(|text| excerpt(text, written)).unwrap_or_default(),
+                    ..source.clone()
+                });
+        }
+    }
+
+    /// Record names found in one source under their canonical form. Excerpts
+    /// are found by the text as written; the highest likelihood seen is kept.
+    pub fn add_names(&mut self, names: &[NameMatch], source: &EntitySource, texts: &[&str]) {
+        for found in names {
+            let entity = self
+                .entities
+                .entry(found.name.clone())
+                .or_insert_with(|| Entity::new(&found.name));
+            entity.add_source(EntitySource {
+                mentions: 1,
+                excerpt: texts.iter().find_map(|text| excerpt(text, &found.matched)).unwrap_or_default(),
+                ..source.clone()
+            });
+            entity.likelihood = Some(entity.likelihood.map_or(found.likelihood, |l| l.max(found.likelihood)));
+        }
+    }
+
+    /// The top `max` entities scoring at least `min_confidence`, most
+    /// confident first
+    pub fn into_ranked(self, max: usize, min_confidence: f64) -> Vec<Entity> {
+        let mut entities: Vec<Entity> = self
+            .entities
+            .into_values()
+            .filter(|e| e.confidence >= min_confidence)
+            .collect();
+        rank(&mut entities);
+        entities.truncate(max);
+        entities
+    }
+}
+
+/// The first place `value` appears in `text` (ignoring case, and allowing
+/// "City, ST" to match "City ST"), highlighted with up to
+/// `EXCERPT_CONTEXT` characters either side, cut at word boundaries
+pub fn excerpt(text: &str, value: &str) -> Option<String> {
+    let pattern = regex::escape(value).replace(", ", r",?\s+");
+    let found = Regex::new(&format!("(?i){}", pattern)).ok()?.find(text)?;
+
+    let before = &text[..found.start()];
+    let mut start = before
+        .char_indices()
+        .rev()
+        .nth(EXCERPT_CONTEXT - 1)
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+    if start > 0 {
+        if let Some(space) = before[start..].find(char::is_whitespace) {
+            start += space;
+        }
+    }
+
+    let after = &text[found.end()..];
+    let mut end = after
+        .char_indices()
+        .nth(EXCERPT_CONTEXT)
+        .map(|(i, _)| i)
+        .unwrap_or(after.len());
+    if end < after.len() {
+        if let Some(space) = after[..end].rfind(char::is_whitespace) {
+            end = space;
+        }
+    }
+
+    Some(format!(
+        "{}{}{}{}{}{}{}",
+        if start > 0 { "…" } else { "" },
+        before[start..].trim_start(),
+        HIGHLIGHT,
+        found.as_str(),
+        HIGHLIGHT,
+        after[..end].trim_end(),
+        if end < after.len() { "…" } else { "" },
+    ))
+}
+
+fn rank(entities: &mut [Entity]) {
+    entities.sort_by(|a, b| {
+        b.confidence.total_cmp(&a.confidence).then_with(|| {
+            (Reverse(a.pages), Reverse(a.mentions), &a.value)
+                .cmp(&(Reverse(b.pages), Reverse(b.mentions), &b.value))
+        })
+    });
+}
+
+/// Combine entities whose values are equal (e.g. two names with the same
+/// initials after redaction), merging their sources and re-ranking
+pub fn merge_entities<I: IntoIterator<Item = Entity>>(entities: I) -> Vec<Entity> {
+    let mut merged: Vec<Entity> = Vec::new();
+    for entity in entities {
+        match merged.iter_mut().find(|e| e.value == entity.value) {
+            Some(existing) => {
+                for source in entity.sources {
+                    existing.add_source(source);
+                }
+            }
+            None => merged.push(entity),
+        }
+    }
+    rank(&mut merged);
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tally_counts_mentions_pages_and_engines() {
+        let mut tally = EntityTally::new();
+        let john = vec!["John Smith".to_string()];
+        let jane = vec!["Jane Doe".to_string(); 3];
+        let page_a = EntitySource::new("a.com/1", "https://a.com/1", vec!["Google".to_string()], SourceKind::Web, true);
+        let page_b = EntitySource::new("b.com/2", "https://b.com/2", vec!["Bing".to_string(), "Google".to_string()], SourceKind::Web, true);
+
+        tally.add(&jane, &page_a, &[]);
+        tally.add(&john, &page_a, &["Owner: John Smith"]);
+        tally.add(&john, &page_b, &[]);
+
+        let ranked = tally.into_ranked(10, 0.0);
+        assert_eq!(ranked[0].value, "John Smith");
+        assert_eq!((ranked[0].mentions, ranked[0].pages, ranked[0].engines), (2, 2, 2));
+        assert_eq!((ranked[1].mentions, ranked[1].pages, ranked[1].engines), (3, 1, 1));
+        assert_eq!(ranked[1].sources[0].mentions, 3);
+        assert_eq!(ranked[0].sources[0].excerpt, "Owner: **John Smith**");
+
+        let merged = merge_entities(ranked.into_iter().map(|mut e| {
+            e.value = "X".to_string();
+            e
+        }));
+        assert_eq!(merged.len(), 1);
+        assert_eq!((merged[0].mentions, merged[0].pages), (5, 2));
+    }
+
+    #[test]
+    fn test_confidence_weights_sources() {
+        let web = |key: &str, engine: &str, verified: bool| {
+            EntitySource { mentions: 1, ..EntitySource::new(key, "", vec![engine.to_string()], SourceKind::Web, verified) }
+        };
+        let mut single = Entity::new("A");
+        single.add_source(web("a", "Google", true));
+        assert_eq!(single.confidence, 0.525);
+
+        let mut unverified_directory = Entity::new("B");
+        unverified_directory.add_source(EntitySource {
+            mentions: 1,
+            ..EntitySource::new("b", "", vec!["Google".to_string()], SourceKind::Directory, false)
+        });
+        assert!(unverified_directory.confidence < single.confidence);
+
+        let mut corroborated = single.clone();
+        corroborated.add_source(web("c", "Bing", true));
+        corroborated.add_source(EntitySource {
+            mentions: 1,
+            ..EntitySource::new("d", "", vec!["Whitepages".to_string()], SourceKind::PeopleSearch, true)
+        });
+        assert!(corroborated.confidence > 0.9 && corroborated.confidence <= 1.0);
+
+        // A directory page agreeing with a web page is no extra evidence
+        let mut two_web = single.clone();
+        two_web.add_source(web("e", "Bing", true));
+        let mut web_and_directory = single.clone();
+        web_and_directory.add_source(EntitySource {
+            mentions: 1,
+            ..EntitySource::new("e", "", vec!["Bing".to_string()], SourceKind::Directory, true)
+        });
+        assert_eq!(web_and_directory.confidence, two_web.confidence);
+
+        let mut tally = EntityTally::new();
+        tally.add(&["A".to_string()], &web("a", "Google", false), &[]);
+        assert!(tally.into_ranked(10, 0.5).is_empty());
+    }
+
+    #[test]
+    fn test_excerpt_highlights_with_context() {
+        assert_eq!(
+            excerpt("Lives in philadelphia PA since 2010", "Philadelphia, PA").as_deref(),
+            Some("Lives in **philadelphia PA** since 2010")
+        );
+        let long = format!("{} John Smith {}", "word ".repeat(30), "tail ".repeat(30));
+        let cut = excerpt(&long, "John Smith").unwrap();
+        assert!(cut.starts_with("…word") && cut.ends_with("tail…"), "{}", cut);
+        assert!(cut.contains("**John Smith**"));
+        assert_eq!(excerpt("nothing here", "Jane Doe"), None);
+    }
+}
@@ -0,0 +1,244 @@
+use anyhow::{anyhow, bail, Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::config::config_dir;
+
+/// File name of the user and per-case exclusion layers
+pub const EXCLUSIONS_FILE: &str = "exclusions.txt";
+
+/// Defaults every layer starts from
+const BUILTIN: &str = include_str!("data/exclusions.txt");
+
+/// A `[section]` of an exclusions file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    /// Whole candidates never reported as names
+    Words,
+    Usernames,
+    /// Emails containing one of these are dropped
+    EmailDomains,
+    /// Emails starting with one of these are dropped
+    EmailPrefixes,
+    /// Exact email addresses
+    Emails,
+}
+
+impl Section {
+    const ALL: [Section; 5] = [
+        Section::Words,
+        Section::Usernames,
+        Section::EmailDomains,
+        Section::EmailPrefixes,
+        Section::Emails,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Section::Words => "words",
+            Section::Usernames => "usernames",
+            Section::EmailDomains => "email_domains",
+            Section::EmailPrefixes => "email_prefixes",
+            Section::Emails => "emails",
+        }
+    }
+}
+
+/// Words, usernames and emails the extractors ignore. Built from the
+/// bundled defaults plus any number of layers, each able to add or remove
+/// entries. Entries are stored lowercased and matched without case.
+#[derive(Debug, Clone)]
+pub struct Exclusions {
+    sections: [HashSet<String>; 5],
+}
+
+impl Default for Exclusions {
+    /// The built-in lists only
+    fn default() -> Self {
+        let mut exclusions = Exclusions {
+            sections: Default::default(),
+        };
+        exclusions
+            .apply(BUILTIN)
+            .expect("built-in exclusion list is valid");
+        exclusions
+    }
+}
+
+impl Exclusions {
+    /// Built-in lists, then each layer file that exists, in order
+    pub fn load(layers: &[PathBuf]) -> Result<Self> {
+        let mut exclusions = Exclusions::default();
+        for path in layers.iter().filter(|path| path.exists()) {
+            let contents = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read exclusion list {}", path.display()))?;
+            exclusions
+                .apply(&contents)
+                .with_context(|| format!("Invalid exclusion list {}", path.display()))?;
+        }
+        Ok(exclusions)
+    }
+
+    /// Apply one layer: `[section]` headers, then one entry per line.
+    /// "+entry" or a plain line adds, "-entry" removes. Blank lines and
+    /// lines starting with `#` are ignored.
+    pub fn apply(&mut self, contents: &str) -> Result<()> {
+        let mut section = None;
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = Some(
+                    Section::ALL
+                        .into_iter()
+                        .find(|s| s.as_str() == name.trim())
+                        .ok_or_else(|| anyhow!("line {}: unknown section [{}]", i + 1, name))?,
+                );
+                continue;
+            }
+            let Some(section) = section else {
+                bail!("line {}: entry before any [section]", i + 1);
+            };
+            match line.strip_prefix('-') {
+                Some(entry) => self.remove(section, entry),
+                None => self.add(section, line.strip_prefix('+').unwrap_or(line)),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn add(&mut self, section: Section, entry: &str) {
+        let entry = entry.trim().to_lowercase();
+        if !entry.is_empty() {
+            self.sections[section as usize].insert(entry);
+        }
+    }
+
+    pub fn remove(&mut self, section: Section, entry: &str) {
+        self.sections[section as usize].remove(&entry.trim().to_lowercase());
+    }
+
+    fn contains(&self, section: Section, value: &str) -> bool {
+        self.sections[section as usize].contains(&value.to_lowercase())
+    }
+
+    /// Whether a name candidate is on the word list
+    pub fn excludes_name(&self, name: &str) -> bool {
+        self.contains(Section::Words, name)
+    }
+
+    pub fn excludes_username(&self, username: &str) -> bool {
+        self.contains(Section::Usernames, username)
+    }
+
+    /// Placeholder domains, automated senders and listed addresses
+    pub fn excludes_email(&self, email: &str) -> bool {
+        let email = email.to_lowercase();
+        self.contains(Section::Emails, &email)
+            || self.sections[Section::EmailDomains as usize]
+                .iter()
+                .any(|domain| email.contains(domain.as_str()))
+            || self.sections[Section::EmailPrefixes as usize]
+                .iter()
+                .any(|prefix| email.starts_with(prefix.as_str()))
+    }
+}
+
+/// The user layer: the configured path or `~/.config/telespotter/exclusions.txt`
+pub fn user_layer(configured: Option<&str>) -> Option<PathBuf> {
+    configured
+        .map(PathBuf::from)
+        .or_else(|| config_dir().map(|dir| dir.join(EXCLUSIONS_FILE)))
+}
+
+/// The per-case layer, kept in the case's folder
+pub fn case_layer(case_dir: &Path) -> PathBuf {
+    case_dir.join(EXCLUSIONS_FILE)
+}
+
+/// Append entries to a layer file, grouped under their sections and headed
+/// by a comment saying where they came from. Returns how many were written.
+pub fn promote(path: &Path, entries: &[(Section, String)], origin: &str) -> Result<usize> {
+    if entries.iter().all(|(_, entry)| entry.trim().is_empty()) {
+        return Ok(0);
+    }
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+
+    let mut block = format!("\n# {}\n", origin);
+    let mut written = 0;
+    for section in Section::ALL {
+        let lines: Vec<&str> = entries
+            .iter()
+            .filter(|(s, _)| *s == section)
+            .map(|(_, entry)| entry.trim())
+            .filter(|entry| !entry.is_empty())
+            .collect();
+        if !lines.is_empty() {
+            block.push_str(&format!("[{}]\n", section.as_str()));
+            written += lines.len();
+            for line in lines {
+                block.push_str(&format!("+{}\n", line));
+            }
+        }
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open exclusion list {}", path.display()))?;
+    file.write_all(block.as_bytes())?;
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layers_add_and_remove() {
+        let mut exclusions = Exclusions::default();
+        assert!(exclusions.excludes_name("new york"));
+        assert!(exclusions.excludes_username("share"));
+        assert!(exclusions.excludes_email("NoReply@shop.com"));
+        assert!(exclusions.excludes_email("jo@example.com"));
+
+        exclusions
+            .apply("[words]\n-New York\n+Best Price\n[usernames]\nacme\n[emails]\ninfo@acme.com\n")
+            .unwrap();
+        assert!(!exclusions.excludes_name("New York"));
+        assert!(exclusions.excludes_name("Best Price"));
+        assert!(exclusions.excludes_username("Acme"));
+        assert!(exclusions.excludes_email("info@acme.com"));
+        assert!(!exclusions.excludes_email("sales@acme.com"));
+
+        assert!(exclusions.apply("Orphan").is_err());
+        assert!(exclusions.apply("[places]\nDover").is_err());
+    }
+
+    #[test]
+    fn test_promote_appends_a_layer() {
+        let path = std::env::temp_dir().join(format!("telespotter_exclusions_{}.txt", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let entries = vec![
+            (Section::Usernames, "acme".to_string()),
+            (Section::Words, "Best Price".to_string()),
+            (Section::Emails, "  ".to_string()),
+        ];
+        assert_eq!(promote(&path, &entries, "Promoted from review.json").unwrap(), 2);
+
+        let exclusions = Exclusions::load(std::slice::from_ref(&path)).unwrap();
+        assert!(exclusions.excludes_name("Best Price"));
+        assert!(exclusions.excludes_username("acme"));
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("# Promoted from review.json\n[words]\n+Best Price\n[usernames]\n+acme\n"));
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -13,6 +13,7 @@ struct GoogleApiResponse {
 struct GoogleSearchItem {
     title: String,
     snippet: Option<String>,
+    link: Option<String>,
 }
 
 /// Search using Google Custom Search API if credentials are available,
@@ -60,9 +61,8 @@ async fn search_with_api_config(
     config: &SearchConfig,
 ) -> Result<Vec<SearchResult>> {
     let client = create_client_from_config(config);
-    // Wrap query in quotes for exact phrase matching
-    let quoted_query = format!("\"{}\"", query);
-    let encoded_query = urlencoding::encode(&quoted_query);
+    // Query is sent as-is; quoting comes from the query template
+    let encoded_query = urlencoding::encode(query);
 
     // Google Custom Search API endpoint
     let url = format!(
@@ -88,6 +88,7 @@ async fn search_with_api_config(
                 item.snippet.unwrap_or_default(),
                 "Google".to_string(),
             )
+            .with_url(item.link.unwrap_or_default())
         })
         .collect();
 
@@ -103,9 +104,8 @@ async fn search_with_scraping(query: &str, num_results: usize) -> Result<Vec<Sea
 /// Search using web scraping with config
 async fn search_with_scraping_config(query: &str, num_results: usize, config: &SearchConfig) -> Result<Vec<SearchResult>> {
     let client = create_client_from_config(config);
-    // Wrap query in quotes for exact phrase matching
-    let quoted_query = format!("\"{}\"", query);
-    let encoded_query = urlencoding::encode(&quoted_query);
+    // Query is sent as-is; quoting comes from the query template
+    let encoded_query = urlencoding::encode(query);
     let url = format!(
         "https://www.google.com/search?q={}&num={}",
         encoded_query, num_results
@@ -125,6 +125,7 @@ async fn search_with_scraping_config(query: &str, num_results: usize, config: &S
     // Selector for main search result divs
     let result_selector = Selector::parse("div.g").unwrap();
     let title_selector = Selector::parse("h3").unwrap();
+    let link_selector = Selector::parse("a[href]").unwrap();
     let snippet_selectors = vec![
         Selector::parse("div.VwiC3b").unwrap(),
         Selector::parse("div.yXK7lf").unwrap(),
@@ -137,6 +138,12 @@ async fn search_with_scraping_config(query: &str, num_results: usize, config: &S
             .map(|e| e.text().collect::<String>())
             .unwrap_or_default();
 
+        let url = element
+            .select(&link_selector)
+            .next()
+            .and_then(|e| e.value().attr("href"))
+            .unwrap_or_default();
+
         let mut snippet = String::new();
         for selector in &snippet_selectors {
             if let Some(s) = element.select(selector).next() {
@@ -150,7 +157,7 @@ async fn search_with_scraping_config(query: &str, num_results: usize, config: &S
                 title,
                 snippet,
                 "Google".to_string(),
-            ));
+            ).with_url(url));
         }
     }
 
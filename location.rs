@@ -0,0 +1,301 @@
+use crate::address::{postal_code, Address};
+use crate::entity::Entity;
+use crate::parser::{nanp_country, state_code, state_name};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// Shown between levels when a hierarchy path is printed on one line
+pub const PATH_SEPARATOR: &str = " › ";
+
+/// A line of `data/zips.txt`
+struct ZipRange {
+    first: u32,
+    last: u32,
+    /// 3 for a ZIP prefix range, 5 for a range of full ZIPs
+    digits: usize,
+    state: &'static str,
+    city: Option<&'static str>,
+}
+
+/// A line of `data/postal_codes.txt`
+struct PostalPrefix {
+    prefix: &'static str,
+    province: &'static str,
+    city: Option<&'static str>,
+}
+
+lazy_static! {
+    static ref ZIP_RANGES: Vec<ZipRange> = load_zip_ranges(include_str!("data/zips.txt"));
+    static ref POSTAL_PREFIXES: Vec<PostalPrefix> = load_postal_prefixes(include_str!("data/postal_codes.txt"));
+}
+
+fn load_postal_prefixes(data: &'static str) -> Vec<PostalPrefix> {
+    data.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, ' ');
+            Some(PostalPrefix {
+                prefix: parts.next()?,
+                province: parts.next()?,
+                city: parts.next(),
+            })
+        })
+        .collect()
+}
+
+fn load_zip_ranges(data: &'static str) -> Vec<ZipRange> {
+    data.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut parts = line.splitn(4, ' ');
+            let first = parts.next()?;
+            Some(ZipRange {
+                first: first.parse().ok()?,
+                last: parts.next()?.parse().ok()?,
+                digits: first.len(),
+                state: parts.next()?,
+                city: parts.next(),
+            })
+        })
+        .collect()
+}
+
+/// State code, and city when the table knows it, for a ZIP ("19102" or
+/// "19102-1234")
+pub fn zip_place(zip: &str) -> Option<(&'static str, Option<&'static str>)> {
+    let five = zip.get(..5).filter(|z| z.chars().all(|c| c.is_ascii_digit()))?;
+    if zip.len() != 5 && !(zip.len() == 10 && zip[5..].starts_with('-')) {
+        return None;
+    }
+    let zip: u32 = five.parse().ok()?;
+    let within = |digits: usize, value: u32| {
+        ZIP_RANGES
+            .iter()
+            .find(move |r| r.digits == digits && (r.first..=r.last).contains(&value))
+    };
+    within(5, zip)
+        .or_else(|| within(3, zip / 100))
+        .map(|r| (r.state, r.city))
+}
+
+/// Province code, and city when the table knows it, for a Canadian postal
+/// code in "A1A 1A1" form
+pub fn postal_place(code: &str) -> Option<(&'static str, Option<&'static str>)> {
+    POSTAL_PREFIXES
+        .iter()
+        .filter(|p| code.starts_with(p.prefix))
+        .max_by_key(|p| p.prefix.len())
+        .map(|p| (p.province, p.city))
+}
+
+/// Region and city for a ZIP or postal code
+fn code_place(code: &str) -> Option<(&'static str, Option<&'static str>)> {
+    zip_place(code).or_else(|| postal_place(code))
+}
+
+/// Where a location or address sits in the state › city › ZIP hierarchy.
+/// For Canada the levels are province › city › postal code; Caribbean
+/// countries have no levels below the country.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Place {
+    /// State or province code, or a country name
+    pub state: String,
+    pub city: Option<String>,
+    pub zip: Option<String>,
+}
+
+/// Place a location value ("PA", "Pennsylvania", "Philadelphia, PA",
+/// "19102", "M5V 2T6", "Jamaica") or address. ZIPs and postal codes fill in
+/// a missing state and city; a written city is kept over the table's.
+pub fn resolve(value: &str) -> Option<Place> {
+    if let Some(region) = state_code(value).or_else(|| nanp_country(value)) {
+        return Some(Place {
+            state: region.to_string(),
+            ..Default::default()
+        });
+    }
+    if let Some((state, city)) = zip_place(value) {
+        return Some(Place {
+            state: state.to_string(),
+            city: city.map(str::to_string),
+            zip: Some(value[..5].to_string()),
+        });
+    }
+    if let Some(code) = postal_code(value) {
+        let (province, city) = postal_place(&code)?;
+        return Some(Place {
+            state: province.to_string(),
+            city: city.map(str::to_string),
+            zip: Some(code),
+        });
+    }
+    let address = Address::parse(value)?;
+    let from_zip = address.zip.as_deref().and_then(code_place);
+    let state = address.state.or_else(|| from_zip.map(|(state, _)| state.to_string()))?;
+    let city = address.city.or_else(|| {
+        from_zip
+            .filter(|(zip_state, _)| *zip_state == state)
+            .and_then(|(_, city)| city.map(str::to_string))
+    });
+    Some(Place {
+        state,
+        city,
+        zip: address.zip,
+    })
+}
+
+/// One state, city or ZIP with the places inside it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocationNode {
+    pub name: String,
+    /// Distinct pages mentioning this place or anywhere inside it
+    pub count: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<LocationNode>,
+}
+
+impl LocationNode {
+    /// "Pennsylvania (7)"
+    pub fn label(&self) -> String {
+        format!("{} ({})", self.name, self.count)
+    }
+
+    /// The best-supported path down from this node:
+    /// "Pennsylvania (7) › Philadelphia (5) › 19102 (3)"
+    pub fn top_path(&self) -> String {
+        let mut path = vec![self.label()];
+        let mut node = self;
+        while let Some(child) = node.children.first() {
+            path.push(child.label());
+            node = child;
+        }
+        path.join(PATH_SEPARATOR)
+    }
+}
+
+/// Pages seen for a place, by sub-place
+#[derive(Default)]
+struct PlaceTally {
+    pages: HashSet<String>,
+    children: BTreeMap<String, PlaceTally>,
+}
+
+impl PlaceTally {
+    fn into_nodes(children: BTreeMap<String, PlaceTally>) -> Vec<LocationNode> {
+        let mut nodes: Vec<LocationNode> = children
+            .into_iter()
+            .map(|(name, tally)| LocationNode {
+                name,
+                count: tally.pages.len(),
+                children: PlaceTally::into_nodes(tally.children),
+            })
+            .collect();
+        // BTreeMap order breaks ties by name
+        nodes.sort_by_key(|node| std::cmp::Reverse(node.count));
+        nodes
+    }
+}
+
+/// Roll location and address entities up into a state › city › ZIP tree.
+/// Codes and names of a state are one node, ZIPs are placed through the
+/// bundled ZIP table, and each node counts the distinct pages mentioning
+/// it or anything inside it. ZIPs whose city is unknown sit under the state.
+pub fn build_hierarchy<'a, I: IntoIterator<Item = &'a Entity>>(entities: I) -> Vec<LocationNode> {
+    let mut states: BTreeMap<String, PlaceTally> = BTreeMap::new();
+    for entity in entities {
+        let Some(place) = resolve(&entity.value) else {
+            continue;
+        };
+        let name = state_name(&place.state).unwrap_or(&place.state).to_string();
+        let pages = entity.sources.iter().map(|s| s.key.clone());
+        let mut level = states.entry(name).or_default();
+        level.pages.extend(pages.clone());
+        for child in [place.city, place.zip].into_iter().flatten() {
+            level = level.children.entry(child).or_default();
+            level.pages.extend(pages.clone());
+        }
+    }
+    PlaceTally::into_nodes(states)
+}
+
+/// Drop nodes (and everything under them) whose name fails `keep`
+pub fn retain_nodes(nodes: &mut Vec<LocationNode>, keep: &impl Fn(&str) -> bool) {
+    nodes.retain(|node| keep(&node.name));
+    for node in nodes {
+        retain_nodes(&mut node.children, keep);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{EntitySource, SourceKind};
+
+    fn entity(value: &str, pages: &[&str]) -> Entity {
+        let mut entity = Entity::new(value);
+        for page in pages {
+            entity.add_source(EntitySource {
+                mentions: 1,
+                ..EntitySource::new(page, page, vec!["Google".to_string()], SourceKind::Web, true)
+            });
+        }
+        entity
+    }
+
+    #[test]
+    fn test_resolve_locations() {
+        assert_eq!(zip_place("19102"), Some(("PA", Some("Philadelphia"))));
+        assert_eq!(zip_place("17101-1234"), Some(("PA", None)));
+        assert_eq!(zip_place("1910"), None);
+        assert_eq!(resolve("Pennsylvania").unwrap().state, "PA");
+        assert_eq!(
+            resolve("12 Oak Ave, 19103"),
+            Some(Place {
+                state: "PA".to_string(),
+                city: Some("Philadelphia".to_string()),
+                zip: Some("19103".to_string()),
+            })
+        );
+        assert_eq!(resolve("Springfield"), None);
+
+        assert_eq!(postal_place("M5V 2T6"), Some(("ON", Some("Toronto"))));
+        assert_eq!(postal_place("X0A 0H0"), Some(("NU", None)));
+        assert_eq!(resolve("v6b1a1").unwrap().city.as_deref(), Some("Vancouver"));
+        assert_eq!(resolve("Jamaica").unwrap().state, "Jamaica");
+    }
+
+    #[test]
+    fn test_build_hierarchy_rolls_up_pages() {
+        let entities = vec![
+            entity("PA", &["a", "b"]),
+            entity("Pennsylvania", &["c"]),
+            entity("Philadelphia, PA", &["a", "d"]),
+            entity("19102", &["d", "e"]),
+            entity("17101", &["f"]),
+            entity("123 Main St, Philadelphia, PA 19102", &["g"]),
+            entity("Dover, DE", &["h"]),
+            entity("Toronto, ON", &["i"]),
+            entity("M5V 2T6", &["i"]),
+            entity("Jamaica", &["j"]),
+        ];
+        let hierarchy = build_hierarchy(&entities);
+        assert_eq!(hierarchy.len(), 4);
+        assert_eq!(
+            hierarchy[0].top_path(),
+            "Pennsylvania (7) › Philadelphia (4) › 19102 (3)"
+        );
+        assert_eq!(hierarchy[0].children[1].label(), "17101 (1)");
+        let paths: Vec<String> = hierarchy[1..].iter().map(LocationNode::top_path).collect();
+        assert_eq!(
+            paths,
+            vec!["Delaware (1) › Dover (1)", "Jamaica (1)", "Ontario (1) › Toronto (1) › M5V 2T6 (1)"]
+        );
+
+        let mut without_zips = hierarchy.clone();
+        retain_nodes(&mut without_zips, &|name| !name.starts_with('1'));
+        assert_eq!(without_zips[0].top_path(), "Pennsylvania (7) › Philadelphia (4)");
+    }
+}
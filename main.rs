@@ -1,9 +1,10 @@
 use chrono::Utc;
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
 use colored::*;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tokio::time::sleep;
 
@@ -13,7 +14,26 @@ mod google;
 mod bing;
 mod duckduckgo;
 mod parser;
+mod names;
+mod cluster;
+mod address;
+mod location;
+mod exclusion;
 mod analysis;
+mod dedup;
+mod entity;
+mod person;
+mod config;
+mod query;
+mod spam;
+mod peoplesearch;
+mod purpose;
+mod audit;
+mod protected;
+mod case;
+mod redact;
+mod crypt;
+mod retention;
 
 // People search site modules
 mod whitepages;
@@ -25,6 +45,21 @@ mod usphonebook;
 use crate::phone::PhoneFormatter;
 use crate::search::{SearchResult, SearchConfig};
 use crate::analysis::PatternAnalyzer;
+use crate::cluster::Review;
+use crate::exclusion::Exclusions;
+use crate::config::Config;
+use crate::query::{available_templates, build_queries, resolve_templates, MatchMode};
+use crate::person::PersonRecord;
+use crate::parser::EntityKind;
+use crate::spam::SpamClassifier;
+use crate::peoplesearch::RateLimiter;
+use crate::purpose::Authorization;
+use crate::audit::{AuditEntry, RunRecord};
+use crate::protected::ProtectedList;
+use crate::case::{CaseInfo, Manifest};
+use crate::redact::{RedactLevel, Redactor};
+use crate::dedup::MergedResult;
+use crate::crypt::Encryption;
 
 const ASCII_LOGO: &str = r#"
 ████████╗███████╗██╗     ███████╗███████╗██████╗  ██████╗ ████████╗████████╗███████╗██████╗ 
@@ -54,9 +89,75 @@ pub enum Engine {
     All,
 }
 
+/// Maintenance subcommands
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Add a number to the do-not-search list (stored as a salted hash)
+    Protect {
+        /// Phone number that must never be looked up
+        number: String,
+    },
+    /// Decrypt an encrypted results/evidence file to disk
+    Decrypt {
+        /// Encrypted file (.age)
+        file: String,
+        /// Where to write the plaintext (default: file name without .age)
+        #[arg(short = 'o', long)]
+        output: Option<String>,
+        /// age identity file for key-encrypted files (default: config "identity_file")
+        #[arg(long)]
+        identity: Option<String>,
+    },
+    /// Print an encrypted results/evidence file without writing plaintext to disk
+    View {
+        /// Encrypted file (.age)
+        file: String,
+        /// age identity file for key-encrypted files (default: config "identity_file")
+        #[arg(long)]
+        identity: Option<String>,
+    },
+    /// Securely delete saved data older than the retention period
+    Purge {
+        /// List what would be deleted without deleting it
+        #[arg(long)]
+        dry_run: bool,
+        /// Override the configured retention period in days
+        #[arg(long)]
+        days: Option<u32>,
+    },
+    /// Add the entities under "exclude" in a review file to an exclusion list
+    Exclude {
+        /// Review file (JSON) with "exclude": {"names", "usernames", "emails"}
+        review: PathBuf,
+        /// Write to this case's exclusion list instead of the user's
+        #[arg(long)]
+        case: Option<String>,
+    },
+    /// Audit log tools
+    Audit {
+        #[command(subcommand)]
+        action: AuditCommand,
+    },
+}
+
+/// Audit log subcommands
+#[derive(Subcommand, Debug)]
+enum AuditCommand {
+    /// Check the audit log's hash chain for edits or deletions
+    Verify {
+        /// Audit log to check (default: configured audit log)
+        #[arg(long)]
+        file: Option<String>,
+    },
+}
+
 #[derive(Parser, Debug)]
 #[command(author, version, about = "Phone Number OSINT Search Tool", long_about = None)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Phone number to search
     #[arg(help = "Phone number (digits only or formatted)")]
     phone_number: Option<String>,
@@ -81,10 +182,22 @@ struct Args {
     #[arg(long, default_value = "1")]
     delay: u64,
 
-    /// Custom output file path (default: telespotter_results_<phone>.json)
+    /// Custom output file path (default: cases/<case>/<number>/<timestamp>/telespotter_<case>_<number>.json)
     #[arg(short = 'o', long)]
     output: Option<String>,
 
+    /// Case id for case management (default: config "case_id")
+    #[arg(long)]
+    case_id: Option<String>,
+
+    /// Investigator name (default: config "investigator")
+    #[arg(long)]
+    investigator: Option<String>,
+
+    /// Free-text note stored with the run (default: config "note")
+    #[arg(long)]
+    note: Option<String>,
+
     /// Output format: json, csv, or txt
     #[arg(short = 'f', long, value_enum, default_value = "json")]
     format: OutputFormat,
@@ -93,14 +206,69 @@ struct Args {
     #[arg(short = 'e', long, value_enum, default_value = "all")]
     engines: Vec<Engine>,
 
+    /// Query template preset name or literal template containing {fmt}
+    /// (can specify multiple: -T exact -T facebook)
+    #[arg(short = 'T', long = "template", default_value = "exact")]
+    templates: Vec<String>,
+
+    /// Match mode: exact (quoted), broad (unquoted, post-filtered), or both
+    #[arg(short = 'm', long = "match", value_enum, default_value = "exact")]
+    match_mode: MatchMode,
+
+    /// List available query template presets and exit
+    #[arg(long)]
+    list_templates: bool,
+
+    /// Path to config file (default: ~/.config/telespotter/config.json)
+    #[arg(long, global = true)]
+    config: Option<String>,
+
     /// Quiet mode - minimal output
-    #[arg(short = 'q', long)]
+    #[arg(short = 'q', long, global = true)]
     quiet: bool,
 
     /// Disable colored output
-    #[arg(long)]
+    #[arg(long, global = true)]
     no_color: bool,
 
+    /// Redact personal details in console output and saved files:
+    /// partial (emails, target number) or full (also names and addresses)
+    #[arg(long, value_enum, default_value = "none")]
+    redact: RedactLevel,
+
+    /// Only extract and save these data types (comma-separated:
+    /// names,locations,emails,usernames,ages,phones; default: all)
+    #[arg(long, value_enum, value_delimiter = ',')]
+    collect: Vec<EntityKind>,
+
+    /// Encrypt saved files with a passphrase (prompted, or TELESPOTTER_PASSPHRASE)
+    #[arg(long)]
+    encrypt: bool,
+
+    /// Encrypt saved files to an age public key (age1...); can be repeated
+    #[arg(long = "recipient")]
+    recipients: Vec<String>,
+
+    /// Hide entities scoring below this confidence (0-1)
+    #[arg(long, default_value_t = 0.0, value_parser = parse_confidence)]
+    min_confidence: f64,
+
+    /// Show the evidence behind each entity: source, URL and excerpt
+    #[arg(long)]
+    explain: bool,
+
+    /// JSON file of name merge/split decisions applied when clustering identities
+    #[arg(long, value_name = "FILE")]
+    review: Option<PathBuf>,
+
+    /// Include phone-directory spam results in pattern analysis
+    #[arg(long)]
+    include_spam: bool,
+
+    /// Include results that don't mention the target number in pattern analysis
+    #[arg(long)]
+    include_unverified: bool,
+
     /// Maximum number of names to display in results
     #[arg(long, default_value = "10")]
     max_names: usize,
@@ -149,6 +317,14 @@ struct Args {
     #[arg(short = 'p', long)]
     people_search: bool,
 
+    /// Permissible purpose for people search (required with --people-search)
+    #[arg(long)]
+    purpose: Option<String>,
+
+    /// Operator identifier recorded with people searches (default: config "operator")
+    #[arg(long)]
+    operator: Option<String>,
+
     /// Enable only Whitepages search (use with --people-search)
     #[arg(long)]
     whitepages: bool,
@@ -170,22 +346,49 @@ struct Args {
     usphonebook: bool,
 }
 
+/// Confidence threshold between 0 and 1
+fn parse_confidence(value: &str) -> Result<f64, String> {
+    let confidence: f64 = value.parse().map_err(|_| format!("'{}' is not a number", value))?;
+    if (0.0..=1.0).contains(&confidence) {
+        Ok(confidence)
+    } else {
+        Err("must be between 0 and 1".to_string())
+    }
+}
+
+/// Read a possibly encrypted file and return its plaintext
+fn read_encrypted(file: &str, identity_file: Option<&str>) -> anyhow::Result<Vec<u8>> {
+    let data = fs::read(file)?;
+    let identities = match identity_file {
+        Some(path) if crypt::is_encrypted(&data) => crypt::load_identities(Path::new(path))?,
+        _ => Vec::new(),
+    };
+    crypt::decrypt(&data, &identities)
+}
+
 /// Helper to determine if an engine should be used
 fn should_use_engine(engines: &[Engine], target: Engine) -> bool {
     engines.is_empty() || engines.contains(&Engine::All) || engines.contains(&target)
 }
 
+/// Results of one engine search
+struct EngineOutcome {
+    results: anyhow::Result<Vec<SearchResult>>,
+    /// Requests made, including retries
+    attempts: usize,
+}
+
 /// Search Google with retries
 async fn search_google_with_retry(
     query: &str,
     num_results: usize,
     config: &SearchConfig,
     retries: usize,
-) -> anyhow::Result<Vec<SearchResult>> {
+) -> EngineOutcome {
     let mut last_error = None;
     for attempt in 0..=retries {
         match google::search_with_config(query, num_results, config).await {
-            Ok(results) => return Ok(results),
+            Ok(results) => return EngineOutcome { results: Ok(results), attempts: attempt + 1 },
             Err(e) => {
                 last_error = Some(e);
                 if attempt < retries {
@@ -194,7 +397,7 @@ async fn search_google_with_retry(
             }
         }
     }
-    Err(last_error.unwrap())
+    EngineOutcome { results: Err(last_error.unwrap()), attempts: retries + 1 }
 }
 
 /// Search Bing with retries
@@ -203,11 +406,11 @@ async fn search_bing_with_retry(
     num_results: usize,
     config: &SearchConfig,
     retries: usize,
-) -> anyhow::Result<Vec<SearchResult>> {
+) -> EngineOutcome {
     let mut last_error = None;
     for attempt in 0..=retries {
         match bing::search_with_config(query, num_results, config).await {
-            Ok(results) => return Ok(results),
+            Ok(results) => return EngineOutcome { results: Ok(results), attempts: attempt + 1 },
             Err(e) => {
                 last_error = Some(e);
                 if attempt < retries {
@@ -216,7 +419,7 @@ async fn search_bing_with_retry(
             }
         }
     }
-    Err(last_error.unwrap())
+    EngineOutcome { results: Err(last_error.unwrap()), attempts: retries + 1 }
 }
 
 /// Search DuckDuckGo with retries
@@ -225,11 +428,11 @@ async fn search_duckduckgo_with_retry(
     num_results: usize,
     config: &SearchConfig,
     retries: usize,
-) -> anyhow::Result<Vec<SearchResult>> {
+) -> EngineOutcome {
     let mut last_error = None;
     for attempt in 0..=retries {
         match duckduckgo::search_with_config(query, num_results, config).await {
-            Ok(results) => return Ok(results),
+            Ok(results) => return EngineOutcome { results: Ok(results), attempts: attempt + 1 },
             Err(e) => {
                 last_error = Some(e);
                 if attempt < retries {
@@ -238,7 +441,7 @@ async fn search_duckduckgo_with_retry(
             }
         }
     }
-    Err(last_error.unwrap())
+    EngineOutcome { results: Err(last_error.unwrap()), attempts: retries + 1 }
 }
 
 /// Search a single engine
@@ -248,12 +451,12 @@ async fn search_engine(
     num_results: usize,
     config: &SearchConfig,
     retries: usize,
-) -> anyhow::Result<Vec<SearchResult>> {
+) -> EngineOutcome {
     match engine {
         "google" => search_google_with_retry(query, num_results, config, retries).await,
         "bing" => search_bing_with_retry(query, num_results, config, retries).await,
         "duckduckgo" => search_duckduckgo_with_retry(query, num_results, config, retries).await,
-        _ => Ok(Vec::new()),
+        _ => EngineOutcome { results: Ok(Vec::new()), attempts: 0 },
     }
 }
 
@@ -264,7 +467,7 @@ async fn search_concurrent(
     config: &SearchConfig,
     engines: &[Engine],
     retries: usize,
-) -> Vec<(String, anyhow::Result<Vec<SearchResult>>)> {
+) -> Vec<(String, EngineOutcome)> {
     let mut handles = Vec::new();
 
     if should_use_engine(engines, Engine::Google) {
@@ -293,12 +496,32 @@ async fn search_concurrent(
 
     let mut results = Vec::new();
     for (name, handle) in handles {
-        let result = handle.await.unwrap_or_else(|e| Err(anyhow::anyhow!("Task failed: {}", e)));
-        results.push((name, result));
+        // A task that failed still sent its first request
+        let outcome = handle.await.unwrap_or_else(|e| EngineOutcome {
+            results: Err(anyhow::anyhow!("Task failed: {}", e)),
+            attempts: 1,
+        });
+        results.push((name, outcome));
     }
     results
 }
 
+/// Quote a CSV field, doubling quotes and flattening newlines
+fn csv_field(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\"").replace('\n', " ").replace('\r', ""))
+}
+
+/// One CSV line of quoted fields
+fn csv_row<S: AsRef<str>>(cells: &[S]) -> String {
+    let fields: Vec<String> = cells.iter().map(|cell| csv_field(cell.as_ref())).collect();
+    format!("{}\n", fields.join(","))
+}
+
+/// `results.csv` -> `results.people.csv`
+fn sibling_csv(path: &Path, table: &str) -> PathBuf {
+    path.with_extension(format!("{}.csv", table))
+}
+
 /// Print helper that respects quiet and no-color modes
 macro_rules! qprint {
     ($quiet:expr, $no_color:expr, $colored:expr, $plain:expr) => {
@@ -326,7 +549,7 @@ macro_rules! qprint_inline {
 }
 
 /// Run Sherlock tool on usernames
-fn run_sherlock(usernames: &[String], no_color: bool) -> std::io::Result<()> {
+fn run_sherlock(usernames: &[String], redactor: &Redactor, no_color: bool) -> std::io::Result<()> {
     use std::process::Command;
 
     println!();
@@ -337,10 +560,11 @@ fn run_sherlock(usernames: &[String], no_color: bool) -> std::io::Result<()> {
     }
 
     for username in usernames {
+        let shown = redactor.text(username);
         if no_color {
-            println!("\n  Searching for: @{}", username);
+            println!("\n  Searching for: @{}", shown);
         } else {
-            println!("\n  {} @{}", "Searching for:".yellow(), username.green());
+            println!("\n  {} @{}", "Searching for:".yellow(), shown.green());
         }
 
         let output = Command::new("sherlock")
@@ -353,14 +577,14 @@ fn run_sherlock(usernames: &[String], no_color: bool) -> std::io::Result<()> {
                 if result.status.success() {
                     let stdout = String::from_utf8_lossy(&result.stdout);
                     for line in stdout.lines().take(20) {
-                        println!("    {}", line);
+                        println!("    {}", redactor.text(line));
                     }
                     if stdout.lines().count() > 20 {
                         println!("    ... (truncated, see full output)");
                     }
                 } else {
                     let stderr = String::from_utf8_lossy(&result.stderr);
-                    eprintln!("    Sherlock error: {}", stderr.trim());
+                    eprintln!("    Sherlock error: {}", redactor.text(stderr.trim()));
                 }
             }
             Err(e) => {
@@ -373,7 +597,7 @@ fn run_sherlock(usernames: &[String], no_color: bool) -> std::io::Result<()> {
 }
 
 /// Run Blackbird tool on emails
-fn run_blackbird(emails: &[String], no_color: bool) -> std::io::Result<()> {
+fn run_blackbird(emails: &[String], redactor: &Redactor, no_color: bool) -> std::io::Result<()> {
     use std::process::Command;
 
     println!();
@@ -384,10 +608,11 @@ fn run_blackbird(emails: &[String], no_color: bool) -> std::io::Result<()> {
     }
 
     for email in emails {
+        let shown = redactor.email(email);
         if no_color {
-            println!("\n  Searching for: {}", email);
+            println!("\n  Searching for: {}", shown);
         } else {
-            println!("\n  {} {}", "Searching for:".yellow(), email.green());
+            println!("\n  {} {}", "Searching for:".yellow(), shown.green());
         }
 
         let output = Command::new("blackbird")
@@ -400,11 +625,11 @@ fn run_blackbird(emails: &[String], no_color: bool) -> std::io::Result<()> {
                 if result.status.success() {
                     let stdout = String::from_utf8_lossy(&result.stdout);
                     for line in stdout.lines().take(20) {
-                        println!("    {}", line);
+                        println!("    {}", redactor.text(line));
                     }
                 } else {
                     let stderr = String::from_utf8_lossy(&result.stderr);
-                    eprintln!("    Blackbird error: {}", stderr.trim());
+                    eprintln!("    Blackbird error: {}", redactor.text(stderr.trim()));
                 }
             }
             Err(e) => {
@@ -417,7 +642,7 @@ fn run_blackbird(emails: &[String], no_color: bool) -> std::io::Result<()> {
 }
 
 /// Run email2phonenumber tool
-fn run_email2phone(emails: &[String], no_color: bool) -> std::io::Result<()> {
+fn run_email2phone(emails: &[String], redactor: &Redactor, no_color: bool) -> std::io::Result<()> {
     use std::process::Command;
 
     println!();
@@ -428,10 +653,11 @@ fn run_email2phone(emails: &[String], no_color: bool) -> std::io::Result<()> {
     }
 
     for email in emails {
+        let shown = redactor.email(email);
         if no_color {
-            println!("\n  Looking up: {}", email);
+            println!("\n  Looking up: {}", shown);
         } else {
-            println!("\n  {} {}", "Looking up:".yellow(), email.green());
+            println!("\n  {} {}", "Looking up:".yellow(), shown.green());
         }
 
         let output = Command::new("email2phonenumber")
@@ -446,11 +672,11 @@ fn run_email2phone(emails: &[String], no_color: bool) -> std::io::Result<()> {
                 let stderr = String::from_utf8_lossy(&result.stderr);
                 if !stdout.is_empty() {
                     for line in stdout.lines() {
-                        println!("    {}", line);
+                        println!("    {}", redactor.text(line));
                     }
                 }
                 if !stderr.is_empty() && !result.status.success() {
-                    eprintln!("    {}", stderr.trim());
+                    eprintln!("    {}", redactor.text(stderr.trim()));
                 }
             }
             Err(e) => {
@@ -481,14 +707,21 @@ fn prompt_yes_no(prompt: &str, no_color: bool) -> bool {
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
+    let mut record = RunRecord::default();
+    let result = run(args, &mut record).await;
+    record.finish(&result)?;
+    result
+}
+
+async fn run(args: Args, record: &mut RunRecord) -> anyhow::Result<()> {
 
     // Handle no-color flag
     if args.no_color {
         colored::control::set_override(false);
     }
 
-    // Print logo (unless quiet mode)
-    if !args.quiet {
+    // Print logo (unless quiet mode, or `view` whose output may be piped)
+    if !args.quiet && !matches!(args.command, Some(Command::View { .. })) {
         if args.no_color {
             println!("{}", ASCII_LOGO);
         } else {
@@ -496,6 +729,121 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
+    let user_config = Config::load(args.config.as_deref())?;
+
+    let protected_path = protected::list_path(user_config.protected_numbers.as_deref())?;
+
+    let audit_path = audit::log_path(user_config.audit_log.as_deref())?;
+
+    match &args.command {
+        Some(Command::Protect { number }) => {
+            let digits = PhoneFormatter::new(number)?.national_digits();
+            protected::add(&protected_path, &digits)?;
+            qprint!(args.quiet, args.no_color,
+                format!("Added to protected list: {}", protected_path.display()).green(),
+                format!("Added to protected list: {}", protected_path.display()));
+            return Ok(());
+        }
+        Some(Command::Decrypt { file, output, identity }) => {
+            let plaintext = read_encrypted(file, identity.as_deref().or(user_config.identity_file.as_deref()))?;
+            let path = output.as_ref().map(PathBuf::from).unwrap_or_else(|| crypt::decrypted_path(Path::new(file)));
+            fs::write(&path, plaintext)?;
+            qprint!(args.quiet, args.no_color,
+                format!("Decrypted to: {}", path.display()).green(),
+                format!("Decrypted to: {}", path.display()));
+            return Ok(());
+        }
+        Some(Command::View { file, identity }) => {
+            let plaintext = read_encrypted(file, identity.as_deref().or(user_config.identity_file.as_deref()))?;
+            std::io::stdout().write_all(&plaintext)?;
+            return Ok(());
+        }
+        Some(Command::Purge { dry_run, days }) => {
+            let mut policy = user_config.retention.clone();
+            if let Some(days) = days {
+                policy.days = *days;
+            }
+            let case_root = PathBuf::from(user_config.case_root.as_deref().unwrap_or(case::DEFAULT_CASE_ROOT));
+            let entries = audit::read_entries(&audit_path)?;
+            let plan = retention::plan(&case_root, &policy, &entries, Utc::now())?;
+
+            if *dry_run {
+                if plan.is_empty() {
+                    qprint!(args.quiet, args.no_color,
+                        format!("Nothing older than {} days", policy.days).green(),
+                        format!("Nothing older than {} days", policy.days));
+                    return Ok(());
+                }
+                for dir in &plan.run_dirs {
+                    qprint!(args.quiet, args.no_color, format!("  would purge {}/", dir.display()).yellow(), format!("  would purge {}/", dir.display()));
+                }
+                for file in &plan.files {
+                    qprint!(args.quiet, args.no_color, format!("  would purge {}", file.display()).yellow(), format!("  would purge {}", file.display()));
+                }
+                qprint!(args.quiet, args.no_color,
+                    format!("{} run folder(s) and {} file(s) older than {} days", plan.run_dirs.len(), plan.files.len(), policy.days).green(),
+                    format!("{} run folder(s) and {} file(s) older than {} days", plan.run_dirs.len(), plan.files.len(), policy.days));
+                return Ok(());
+            }
+
+            let removed = retention::execute(&plan)?;
+            let mut entry = AuditEntry::new("purge", "");
+            entry.operator = user_config.operator.clone();
+            entry.files = removed.iter().map(|p| p.display().to_string()).collect();
+            entry.outcome = format!("completed: {} file(s) removed, retention {} days", removed.len(), policy.days);
+            audit::append(&audit_path, entry)?;
+
+            qprint!(args.quiet, args.no_color,
+                format!("Purged {} file(s) older than {} days", removed.len(), policy.days).green(),
+                format!("Purged {} file(s) older than {} days", removed.len(), policy.days));
+            return Ok(());
+        }
+        Some(Command::Exclude { review, case }) => {
+            let entries = Review::load(review)?.exclusions();
+            let path = match case {
+                Some(case_id) => {
+                    let case = CaseInfo::resolve(Some(case_id), None, None, &CaseInfo::default());
+                    let root = PathBuf::from(user_config.case_root.as_deref().unwrap_or(case::DEFAULT_CASE_ROOT));
+                    exclusion::case_layer(&case::case_dir(&root, &case))
+                }
+                None => exclusion::user_layer(user_config.exclusions.as_deref())
+                    .ok_or_else(|| anyhow::anyhow!("Cannot locate exclusion list: set \"exclusions\" in config or HOME"))?,
+            };
+            let origin = format!("Promoted from {} on {}", review.display(), Utc::now().format("%Y-%m-%d"));
+            let added = exclusion::promote(&path, &entries, &origin)?;
+            qprint!(args.quiet, args.no_color,
+                format!("Added {} exclusion(s) to {}", added, path.display()).green(),
+                format!("Added {} exclusion(s) to {}", added, path.display()));
+            return Ok(());
+        }
+        Some(Command::Audit { action: AuditCommand::Verify { file } }) => {
+            let path = file.as_ref().map(PathBuf::from).unwrap_or(audit_path);
+            let report = audit::verify(&path)?;
+            if report.is_valid() {
+                qprint!(args.quiet, args.no_color,
+                    format!("Audit log OK: {} entries, head {}", report.entries, report.head.as_deref().unwrap_or("-")).green(),
+                    format!("Audit log OK: {} entries, head {}", report.entries, report.head.as_deref().unwrap_or("-")));
+                return Ok(());
+            }
+            for problem in &report.problems {
+                qprint!(args.quiet, args.no_color, problem.red(), problem);
+            }
+            anyhow::bail!("Audit log {} failed verification ({} problem(s))", path.display(), report.problems.len());
+        }
+        None => {}
+    }
+
+    if args.list_templates {
+        for template in available_templates(&user_config.query_templates) {
+            if args.no_color {
+                println!("  {:<16} {}", template.name, template.template);
+            } else {
+                println!("  {:<16} {}", template.name.green(), template.template);
+            }
+        }
+        return Ok(());
+    }
+
     if args.debug && !args.quiet {
         qprint!(args.quiet, args.no_color, "🐛 Debug mode enabled\n".yellow(), "Debug mode enabled\n");
     }
@@ -520,9 +868,95 @@ async fn main() -> anyhow::Result<()> {
     let formatter = PhoneFormatter::new(&phone_number)?;
     let formats = formatter.generate_formats();
 
+    let started = Utc::now();
+    let case = CaseInfo::resolve(args.case_id.as_deref(), args.investigator.as_deref(), args.note.as_deref(), &user_config.case);
+
+    // Ask for a passphrase up front rather than after the searches
+    let encryption: Option<Encryption> = if !args.recipients.is_empty() {
+        Some(Encryption::recipients(&args.recipients)?)
+    } else if args.encrypt {
+        Some(Encryption::Passphrase(crypt::read_passphrase("Passphrase for saved files: ")?))
+    } else if !user_config.encryption_recipients.is_empty() {
+        Some(Encryption::recipients(&user_config.encryption_recipients)?)
+    } else {
+        None
+    };
+
+    let redactor = Redactor::new(args.redact, &formatter.national_digits());
+
+    // Every lookup gets an audit entry, written when the run ends
+    let digits = formatter.national_digits();
+    let operator = args.operator.clone().or_else(|| user_config.operator.clone());
+    // The install's key hashes the number wherever it shouldn't appear in the clear:
+    // the audit log if configured, and folder and file names of redacted or encrypted output
+    let hide_number_in_paths = redactor.is_active() || encryption.is_some();
+    let number_key = if user_config.audit_hash_numbers || hide_number_in_paths {
+        Some(audit::load_key(&audit::key_path(&audit_path))?)
+    } else {
+        None
+    };
+    let number_label = case::number_label(&digits, number_key.as_deref().filter(|_| hide_number_in_paths));
+    let audit_number = audit::number_field(&digits, number_key.as_deref().filter(|_| user_config.audit_hash_numbers));
+    let mut entry = AuditEntry::new("lookup", &audit_number);
+    entry.operator = operator.clone();
+    entry.purpose = args.purpose.clone();
+    entry.case_id = case.case_id.clone();
+    record.start(audit_path, entry);
+
+    // Load the review file up front so a typo doesn't waste a whole search
+    let review = match &args.review {
+        Some(path) => Review::load(path)?,
+        None => Review::default(),
+    };
+
+    // Built-in exclusions, then the user's and this case's layers, then the review's
+    let case_root = PathBuf::from(user_config.case_root.as_deref().unwrap_or(case::DEFAULT_CASE_ROOT));
+    let layers: Vec<PathBuf> = exclusion::user_layer(user_config.exclusions.as_deref())
+        .into_iter()
+        .chain(case.case_id.is_some().then(|| exclusion::case_layer(&case::case_dir(&case_root, &case))))
+        .collect();
+    let mut exclusions = Exclusions::load(&layers)?;
+    for (section, entry) in review.exclusions() {
+        exclusions.add(section, &entry);
+    }
+
+    // Protected numbers are never looked up; checked before any network call
+    let protected_list = ProtectedList::load(&protected_path)?;
+    if args.debug && !protected_list.is_empty() {
+        qprint!(args.quiet, args.no_color,
+            format!("Checked against {} protected number(s)", protected_list.len()).yellow(),
+            format!("Checked against {} protected number(s)", protected_list.len()));
+    }
+    if let Some(protected_entry) = protected_list.find(&digits) {
+        record.entry.number = format!("protected:{}", protected_entry.hash);
+        record.entry.outcome = "blocked: protected number".to_string();
+        anyhow::bail!("This number is on the do-not-search list and will not be looked up");
+    }
+
+    // People search needs a permissible purpose and an operator before anything is sent
+    let authorization: Option<Authorization> = if args.people_search {
+        match purpose::authorize(args.purpose.as_deref(), operator.as_deref(), &user_config.permissible_purposes) {
+            Ok(auth) => {
+                record.entry.purpose = Some(auth.purpose.clone());
+                Some(auth)
+            }
+            Err(e) => {
+                record.entry.outcome = format!("refused: {}", e);
+                return Err(e);
+            }
+        }
+    } else {
+        None
+    };
+
+    let templates = resolve_templates(&args.templates, &user_config.query_templates)?;
+    let queries = build_queries(&templates, &formats, args.match_mode);
+
     qprint!(args.quiet, args.no_color,
-        format!("Generated {} search format variations\n", formats.len()).green(),
-        format!("Generated {} search format variations\n", formats.len()));
+        format!("Generated {} search format variations x {} query template(s), match mode: {}\n",
+            formats.len(), templates.len(), args.match_mode.as_str()).green(),
+        format!("Generated {} search format variations x {} query template(s), match mode: {}\n",
+            formats.len(), templates.len(), args.match_mode.as_str()));
 
     // Create search config
     let config = SearchConfig {
@@ -536,23 +970,27 @@ async fn main() -> anyhow::Result<()> {
             "Random user agent rotation enabled");
     }
 
-    // Store all results
+    // Store all results (people search records are kept separately)
     let mut all_results: HashMap<String, Vec<SearchResult>> = HashMap::new();
+    let mut people_records: Vec<PersonRecord> = Vec::new();
+    let spam_classifier = SpamClassifier::new(&user_config.spam_domains);
 
-    // Search each format
-    for (i, format) in formats.iter().enumerate() {
+    // Search each query (template x format)
+    for (i, search_query) in queries.iter().enumerate() {
+        let query = &search_query.query;
         qprint!(args.quiet, args.no_color,
-            format!("[{}/{}] Searching: {}", i + 1, formats.len(), format).blue(),
-            format!("[{}/{}] Searching: {}", i + 1, formats.len(), format));
+            format!("[{}/{}] Searching: {}", i + 1, queries.len(), redactor.text(query)).blue(),
+            format!("[{}/{}] Searching: {}", i + 1, queries.len(), redactor.text(query)));
 
         let mut format_results = Vec::new();
 
         if args.concurrent {
             // Concurrent search mode
-            let results = search_concurrent(format, args.num_results, &config, &args.engines, args.retries).await;
+            let results = search_concurrent(query, args.num_results, &config, &args.engines, args.retries).await;
 
-            for (engine_name, result) in results {
-                match result {
+            for (engine_name, outcome) in results {
+                record.entry.contacted_engine(&engine_name, outcome.attempts);
+                match outcome.results {
                     Ok(res) => {
                         let count = res.len();
                         format_results.extend(res);
@@ -578,7 +1016,9 @@ async fn main() -> anyhow::Result<()> {
                 qprint_inline!(args.quiet, args.no_color,
                     "  → Searching Google... ".cyan(),
                     "  → Searching Google... ");
-                match search_engine("google", format, args.num_results, &config, args.retries).await {
+                let outcome = search_engine("google", query, args.num_results, &config, args.retries).await;
+                record.entry.contacted_engine("Google", outcome.attempts);
+                match outcome.results {
                     Ok(results) => {
                         let count = results.len();
                         format_results.extend(results);
@@ -604,7 +1044,9 @@ async fn main() -> anyhow::Result<()> {
                 qprint_inline!(args.quiet, args.no_color,
                     "  → Searching Bing... ".cyan(),
                     "  → Searching Bing... ");
-                match search_engine("bing", format, args.num_results, &config, args.retries).await {
+                let outcome = search_engine("bing", query, args.num_results, &config, args.retries).await;
+                record.entry.contacted_engine("Bing", outcome.attempts);
+                match outcome.results {
                     Ok(results) => {
                         let count = results.len();
                         format_results.extend(results);
@@ -630,7 +1072,9 @@ async fn main() -> anyhow::Result<()> {
                 qprint_inline!(args.quiet, args.no_color,
                     "  → Searching DuckDuckGo... ".cyan(),
                     "  → Searching DuckDuckGo... ");
-                match search_engine("duckduckgo", format, args.num_results, &config, args.retries).await {
+                let outcome = search_engine("duckduckgo", query, args.num_results, &config, args.retries).await;
+                record.entry.contacted_engine("DuckDuckGo", outcome.attempts);
+                match outcome.results {
                     Ok(results) => {
                         let count = results.len();
                         format_results.extend(results);
@@ -652,27 +1096,58 @@ async fn main() -> anyhow::Result<()> {
             }
         }
 
+        // Engines append their results in order, so rank is a running count per engine
+        let mut ranks: HashMap<String, usize> = HashMap::new();
+        for result in &mut format_results {
+            let rank = ranks.entry(result.source.clone()).or_insert(0);
+            *rank += 1;
+            result.rank = *rank;
+            result.format = search_query.format.clone();
+            result.template = search_query.template.clone();
+            result.match_mode = Some(search_query.match_mode);
+            result.verify(&formatter);
+        }
+
+        // Broad queries are unquoted, so keep only results that contain the number
+        if search_query.match_mode == MatchMode::Broad {
+            let before = format_results.len();
+            format_results.retain(|r| r.verified);
+            let dropped = before - format_results.len();
+            if dropped > 0 {
+                qprint!(args.quiet, args.no_color,
+                    format!("  Broad filter: dropped {} result(s) without the number", dropped).yellow(),
+                    format!("  Broad filter: dropped {} result(s) without the number", dropped));
+            }
+        }
+
+        let flagged = spam_classifier.flag_all(&mut format_results);
+        if flagged > 0 {
+            qprint!(args.quiet, args.no_color,
+                format!("  Flagged {} result(s) as directory spam", flagged).yellow(),
+                format!("  Flagged {} result(s) as directory spam", flagged));
+        }
+
         qprint!(args.quiet, args.no_color,
-            format!("  ✓ Total: {} results for this format", format_results.len()).green(),
-            format!("  Total: {} results for this format", format_results.len()));
+            format!("  ✓ Total: {} results for this query", format_results.len()).green(),
+            format!("  Total: {} results for this query", format_results.len()));
 
         if args.debug && !format_results.is_empty() && !args.quiet {
-            let sample = &format_results[0].title;
+            let sample = &redactor.text(&format_results[0].title);
             let truncated = if sample.chars().count() > 60 {
                 let end_idx = sample.char_indices().nth(60).map(|(i, _)| i).unwrap_or(sample.len());
                 format!("{}...", &sample[..end_idx])
             } else {
-                sample.clone()
+                sample.to_string()
             };
             qprint!(args.quiet, args.no_color,
                 format!("  Debug: Sample - {}", truncated).yellow(),
                 format!("  Debug: Sample - {}", truncated));
         }
 
-        all_results.insert(format.clone(), format_results);
+        all_results.insert(query.clone(), format_results);
 
-        // Rate limiting between formats
-        if i < formats.len() - 1 {
+        // Rate limiting between queries
+        if i < queries.len() - 1 {
             let wait_time = args.delay * 3;
             qprint!(args.quiet, args.no_color,
                 format!("  ⏳ Waiting {} seconds...\n", wait_time).yellow(),
@@ -689,153 +1164,54 @@ async fn main() -> anyhow::Result<()> {
             "\nSearching people lookup sites...".magenta().bold(),
             "\nSearching people lookup sites...");
 
-        let phone_digits: String = phone_number.chars().filter(|c| c.is_numeric()).collect();
-        let mut people_results: Vec<SearchResult> = Vec::new();
-
         // Determine which sites to search (if none specified, search all)
-        let search_all = !args.whitepages && !args.truepeoplesearch &&
-                        !args.fastpeoplesearch && !args.thatsthem && !args.usphonebook;
-
-        // Whitepages
-        if search_all || args.whitepages {
-            qprint_inline!(args.quiet, args.no_color,
-                "  → Searching Whitepages... ".cyan(),
-                "  → Searching Whitepages... ");
-            match whitepages::search_with_config(&phone_digits, &config).await {
-                Ok(results) => {
-                    let count = results.len();
-                    people_results.extend(results);
-                    qprint!(args.quiet, args.no_color,
-                        format!("({} results)", count).green(),
-                        format!("({} results)", count));
-                }
-                Err(e) if args.debug => {
-                    qprint!(args.quiet, args.no_color,
-                        format!("Error: {}", e).yellow(),
-                        format!("Error: {}", e));
-                }
-                Err(_) => {
-                    qprint!(args.quiet, args.no_color,
-                        "(0 results)".yellow(),
-                        "(0 results)");
-                }
-            }
-            sleep(Duration::from_secs(args.delay)).await;
-        }
-
-        // TruePeopleSearch
-        if search_all || args.truepeoplesearch {
-            qprint_inline!(args.quiet, args.no_color,
-                "  → Searching TruePeopleSearch... ".cyan(),
-                "  → Searching TruePeopleSearch... ");
-            match truepeoplesearch::search_with_config(&phone_digits, &config).await {
-                Ok(results) => {
-                    let count = results.len();
-                    people_results.extend(results);
-                    qprint!(args.quiet, args.no_color,
-                        format!("({} results)", count).green(),
-                        format!("({} results)", count));
-                }
-                Err(e) if args.debug => {
-                    qprint!(args.quiet, args.no_color,
-                        format!("Error: {}", e).yellow(),
-                        format!("Error: {}", e));
-                }
-                Err(_) => {
-                    qprint!(args.quiet, args.no_color,
-                        "(0 results)".yellow(),
-                        "(0 results)");
-                }
+        let requested = [
+            ("whitepages", args.whitepages),
+            ("truepeoplesearch", args.truepeoplesearch),
+            ("fastpeoplesearch", args.fastpeoplesearch),
+            ("thatsthem", args.thatsthem),
+            ("usphonebook", args.usphonebook),
+        ];
+        let search_all = requested.iter().all(|(_, enabled)| !enabled);
+        let mut limiter = RateLimiter::new(Duration::from_secs(args.delay));
+
+        for site in peoplesearch::all_sites() {
+            let spec = site.spec();
+            if !search_all && !requested.contains(&(spec.id, true)) {
+                continue;
             }
-            sleep(Duration::from_secs(args.delay)).await;
-        }
 
-        // FastPeopleSearch
-        if search_all || args.fastpeoplesearch {
             qprint_inline!(args.quiet, args.no_color,
-                "  → Searching FastPeopleSearch... ".cyan(),
-                "  → Searching FastPeopleSearch... ");
-            match fastpeoplesearch::search_with_config(&phone_digits, &config).await {
-                Ok(results) => {
-                    let count = results.len();
-                    people_results.extend(results);
+                format!("  → Searching {}... ", spec.name).cyan(),
+                format!("  → Searching {}... ", spec.name));
+            let outcome = peoplesearch::lookup(site, &phone_number, &config, args.retries, &mut limiter).await;
+            record.entry.contacted_site(spec.name, outcome.attempts);
+            match &outcome.error {
+                Some(e) if args.debug => {
+                    let url = redactor.text(&outcome.url);
+                    let e = redactor.text(&e.to_string());
                     qprint!(args.quiet, args.no_color,
-                        format!("({} results)", count).green(),
-                        format!("({} results)", count));
+                        format!("Error after {} attempts ({}): {}", outcome.attempts, url, e).yellow(),
+                        format!("Error after {} attempts ({}): {}", outcome.attempts, url, e));
                 }
-                Err(e) if args.debug => {
-                    qprint!(args.quiet, args.no_color,
-                        format!("Error: {}", e).yellow(),
-                        format!("Error: {}", e));
-                }
-                Err(_) => {
+                Some(_) => {
                     qprint!(args.quiet, args.no_color,
                         "(0 results)".yellow(),
                         "(0 results)");
                 }
-            }
-            sleep(Duration::from_secs(args.delay)).await;
-        }
-
-        // ThatsThem
-        if search_all || args.thatsthem {
-            qprint_inline!(args.quiet, args.no_color,
-                "  → Searching ThatsThem... ".cyan(),
-                "  → Searching ThatsThem... ");
-            match thatsthem::search_with_config(&phone_digits, &config).await {
-                Ok(results) => {
-                    let count = results.len();
-                    people_results.extend(results);
-                    qprint!(args.quiet, args.no_color,
-                        format!("({} results)", count).green(),
-                        format!("({} results)", count));
-                }
-                Err(e) if args.debug => {
+                None => {
                     qprint!(args.quiet, args.no_color,
-                        format!("Error: {}", e).yellow(),
-                        format!("Error: {}", e));
-                }
-                Err(_) => {
-                    qprint!(args.quiet, args.no_color,
-                        "(0 results)".yellow(),
-                        "(0 results)");
+                        format!("({} results)", outcome.records.len()).green(),
+                        format!("({} results)", outcome.records.len()));
                 }
             }
-            sleep(Duration::from_secs(args.delay)).await;
+            people_records.extend(outcome.records);
         }
 
-        // USPhoneBook
-        if search_all || args.usphonebook {
-            qprint_inline!(args.quiet, args.no_color,
-                "  → Searching USPhoneBook... ".cyan(),
-                "  → Searching USPhoneBook... ");
-            match usphonebook::search_with_config(&phone_digits, &config).await {
-                Ok(results) => {
-                    let count = results.len();
-                    people_results.extend(results);
-                    qprint!(args.quiet, args.no_color,
-                        format!("({} results)", count).green(),
-                        format!("({} results)", count));
-                }
-                Err(e) if args.debug => {
-                    qprint!(args.quiet, args.no_color,
-                        format!("Error: {}", e).yellow(),
-                        format!("Error: {}", e));
-                }
-                Err(_) => {
-                    qprint!(args.quiet, args.no_color,
-                        "(0 results)".yellow(),
-                        "(0 results)");
-                }
-            }
-        }
-
-        // Add people search results to all_results
-        if !people_results.is_empty() {
+        if !people_records.is_empty() {
             qprint!(args.quiet, args.no_color,
-                format!("  ✓ Total from people search sites: {} results\n", people_results.len()).green(),
-                format!("  Total from people search sites: {} results\n", people_results.len()));
-            all_results.insert("People Search Sites".to_string(), people_results);
+                format!("  ✓ Total from people search sites: {} records\n", people_records.len()).green(),
+                format!("  Total from people search sites: {} records\n", people_records.len()));
         } else {
             qprint!(args.quiet, args.no_color,
                 "  No results from people search sites\n".yellow(),
@@ -847,12 +1223,27 @@ async fn main() -> anyhow::Result<()> {
     qprint!(args.quiet, args.no_color,
         "Analyzing patterns across all results...".yellow(),
         "Analyzing patterns across all results...");
-    let analyzer = PatternAnalyzer::new();
-    let patterns = analyzer.analyze(&all_results, args.max_names, args.max_locations, args.max_emails, args.max_usernames);
+    let analyzer = PatternAnalyzer::new()
+        .include_spam(args.include_spam)
+        .include_unverified(args.include_unverified)
+        .min_confidence(args.min_confidence)
+        .review(review)
+        .exclusions(exclusions)
+        .collect(if args.collect.is_empty() { &user_config.collect } else { &args.collect });
+    let pages = dedup::merge_results(&all_results);
+    let patterns = analyzer.analyze(&pages, &people_records, args.max_names, args.max_locations, args.max_emails, args.max_usernames);
+
+    // Names found are also hidden wherever they appear in free text
+    let redactor = redactor.with_names(
+        patterns.common_names.iter().map(|name| name.value.clone())
+            .chain(patterns.name_clusters.iter().flat_map(|c| c.members.clone()))
+            .chain(patterns.people_records.iter().map(|r| r.full_name.clone())),
+    );
+    let shown = patterns.redacted(&redactor);
 
     // Print summary (unless quiet mode)
     if !args.quiet {
-        patterns.print_summary(args.no_color);
+        shown.print_summary(args.no_color, args.explain);
     }
 
     // Save results if requested or prompted
@@ -870,104 +1261,264 @@ async fn main() -> anyhow::Result<()> {
     };
 
     if should_save {
-        let digits: String = phone_number.chars().filter(|c| c.is_numeric()).collect();
-
-        // Determine filename
-        let filename = match &args.output {
-            Some(path) => path.clone(),
+        // Everything written below is cut to the collection scope and goes through the redactor
+        let phone_number = redactor.number(&phone_number);
+        let formats: Vec<String> = formats.iter().map(|f| redactor.text(f)).collect();
+        // Keyed by query so every format lists results in the same order
+        let all_results: BTreeMap<String, Vec<SearchResult>> = all_results
+            .iter()
+            .map(|(query, results)| {
+                let results = results.iter().map(|r| redactor.result(&shown.scoped_result(r))).collect();
+                (redactor.text(query), results)
+            })
+            .collect();
+        let pages: Vec<MergedResult> = pages.iter().map(|page| redactor.page(&shown.scoped_page(page))).collect();
+        let people_records = shown.people_records.clone();
+        let patterns = &shown;
+
+        // Determine filename: an explicit path, or the run's case folder
+        let (filename, manifest_path) = match &args.output {
+            Some(path) => (PathBuf::from(path), PathBuf::from(format!("{}.manifest.json", path))),
             None => {
                 let ext = match args.format {
                     OutputFormat::Json => "json",
                     OutputFormat::Csv => "csv",
                     OutputFormat::Txt => "txt",
                 };
-                format!("telespotter_results_{}.{}", digits, ext)
+                let root = user_config.case_root.as_deref().unwrap_or(case::DEFAULT_CASE_ROOT);
+                let dir = case::run_dir(Path::new(root), &case, &number_label, &started);
+                fs::create_dir_all(&dir)?;
+                (dir.join(case::results_file_name(&case, &number_label, ext)), dir.join(case::MANIFEST_FILE))
             }
         };
 
-        // Format and save based on output format
-        match args.format {
+        // Format based on output format; CSV can add sibling files
+        let mut extra_files: Vec<(PathBuf, String)> = Vec::new();
+        let contents = match args.format {
             OutputFormat::Json => {
-                let output = serde_json::json!({
+                let mut output = serde_json::json!({
                     "version": env!("CARGO_PKG_VERSION"),
                     "timestamp": Utc::now().to_rfc3339(),
                     "phone_number": phone_number,
                     "search_formats": formats,
+                    "query_templates": templates,
+                    "match_mode": args.match_mode,
                     "results": all_results,
+                    "pages": pages,
+                    "people_records": people_records,
                     "pattern_analysis": patterns.to_json()
                 });
-                fs::write(&filename, serde_json::to_string_pretty(&output)?)?;
+                if let Some(auth) = &authorization {
+                    output["authorization"] = serde_json::to_value(auth)?;
+                }
+                if !case.is_empty() {
+                    output["case"] = serde_json::to_value(&case)?;
+                }
+                serde_json::to_string_pretty(&output)?
             }
             OutputFormat::Csv => {
-                let mut csv_content = String::from("Source,Title,Snippet\n");
+                let mut csv_content = String::from("Source,Template,Match,URL,Verified,Directory Spam,Title,Snippet\n");
                 for results in all_results.values() {
                     for result in results {
-                        // Escape quotes by doubling them and escape newlines
-                        let title = result.title.replace('"', "\"\"").replace('\n', " ").replace('\r', "");
-                        let snippet = result.snippet.replace('"', "\"\"").replace('\n', " ").replace('\r', "");
-                        let source = result.source.replace('"', "\"\"");
-                        csv_content.push_str(&format!("\"{}\",\"{}\",\"{}\"\n",
-                            source, title, snippet));
+                        let row = [
+                            result.source.as_str(),
+                            result.template.as_str(),
+                            result.match_mode.map(|m| m.as_str()).unwrap_or(""),
+                            result.url.as_str(),
+                            if result.verified { "yes" } else { "no" },
+                            if result.directory_spam { "yes" } else { "" },
+                            result.title.as_str(),
+                            result.snippet.as_str(),
+                        ];
+                        csv_content.push_str(&csv_row(&row));
+                    }
+                }
+
+                // People search records and run details are tables of their own, in sibling files
+                if !people_records.is_empty() {
+                    let mut people_csv = csv_row(person::TABLE_HEADERS);
+                    for record in &people_records {
+                        people_csv.push_str(&csv_row(&record.table_row()));
                     }
+                    extra_files.push((sibling_csv(&filename, "people"), people_csv));
+                }
+
+                let mut details: Vec<(&str, &str)> = Vec::new();
+                if let Some(auth) = &authorization {
+                    details.push(("Purpose", &auth.purpose));
+                    details.push(("Operator", &auth.operator));
+                    details.push(("Authorized At", &auth.timestamp));
                 }
-                fs::write(&filename, csv_content)?;
+                for (field, value) in [("Case ID", &case.case_id), ("Investigator", &case.investigator), ("Note", &case.note)] {
+                    if let Some(value) = value {
+                        details.push((field, value));
+                    }
+                }
+                let scope = patterns.scope_label();
+                if let Some(scope) = &scope {
+                    details.push(("Collected", scope));
+                }
+                if !details.is_empty() {
+                    let mut run_csv = csv_row(&["Field", "Value"]);
+                    for (field, value) in details {
+                        run_csv.push_str(&csv_row(&[field, value]));
+                    }
+                    extra_files.push((sibling_csv(&filename, "run"), run_csv));
+                }
+                csv_content
             }
             OutputFormat::Txt => {
                 let mut txt_content = format!("Telespotter Results for: {}\n", phone_number);
+                if let Some(case_id) = &case.case_id {
+                    txt_content.push_str(&format!("Case ID: {}\n", case_id));
+                }
+                if let Some(investigator) = &case.investigator {
+                    txt_content.push_str(&format!("Investigator: {}\n", investigator));
+                }
+                if let Some(note) = &case.note {
+                    txt_content.push_str(&format!("Note: {}\n", note));
+                }
+                if let Some(scope) = patterns.scope_label() {
+                    txt_content.push_str(&format!("Collected: {}\n", scope));
+                }
+                if redactor.is_active() {
+                    txt_content.push_str(&format!("Redaction: {}\n", redactor.level().as_str()));
+                }
+                if let Some(auth) = &authorization {
+                    txt_content.push_str(&format!("Purpose: {}\nOperator: {}\nAuthorized At: {}\n",
+                        auth.purpose, auth.operator, auth.timestamp));
+                }
                 txt_content.push_str(&"=".repeat(60));
                 txt_content.push('\n');
 
                 txt_content.push_str(&format!("\nTotal Results: {}\n", patterns.total_results));
+                txt_content.push_str(&format!("Unique Pages: {}\n", patterns.unique_results));
+                txt_content.push_str(&format!("Verified Results: {} ({} unverified, {} in analysis)\n",
+                    patterns.verified_results, patterns.unverified_results,
+                    if patterns.unverified_included { "included" } else { "excluded" }));
+
+                if !patterns.results_by_template.is_empty() {
+                    txt_content.push_str("\nResults by Query Template:\n");
+                    let mut templates: Vec<(&String, &usize)> = patterns.results_by_template.iter().collect();
+                    templates.sort();
+                    for (template, count) in templates {
+                        txt_content.push_str(&format!("  - {}: {} results\n", template, count));
+                    }
+                }
 
                 if !patterns.common_names.is_empty() {
                     txt_content.push_str("\nNames Found:\n");
-                    for (name, count) in &patterns.common_names {
-                        txt_content.push_str(&format!("  - {}: {} time(s)\n", name, count));
+                    for name in &patterns.common_names {
+                        txt_content.push_str(&format!("  - {}: {}\n", name.value, name.summary()));
+                    }
+                }
+
+                let identities: Vec<_> = patterns.name_clusters.iter().filter(|c| c.members.len() > 1).collect();
+                if !identities.is_empty() {
+                    txt_content.push_str("\nIdentities (name variants combined):\n");
+                    for cluster in identities {
+                        txt_content.push_str(&format!("  - {}: {}\n", cluster.label, cluster.evidence.summary()));
+                        txt_content.push_str(&format!("      spellings: {}\n", cluster.members.join(", ")));
                     }
                 }
 
                 if !patterns.common_locations.is_empty() {
                     txt_content.push_str("\nLocations Found:\n");
-                    for (loc, count) in &patterns.common_locations {
-                        txt_content.push_str(&format!("  - {}: {} occurrence(s)\n", loc, count));
+                    for loc in &patterns.common_locations {
+                        txt_content.push_str(&format!("  - {}: {}\n", loc.value, loc.summary()));
+                    }
+                }
+
+                if !patterns.location_hierarchy.is_empty() {
+                    txt_content.push_str("\nLocation Hierarchy:\n");
+                    for state in &patterns.location_hierarchy {
+                        txt_content.push_str(&format!("  - {}\n", state.top_path()));
+                    }
+                }
+
+                if !patterns.addresses.is_empty() {
+                    txt_content.push_str("\nAddresses Found:\n");
+                    for address in &patterns.addresses {
+                        txt_content.push_str(&format!("  - {}: {}\n", address.value, address.summary()));
                     }
                 }
 
                 if !patterns.emails.is_empty() {
                     txt_content.push_str("\nEmails Found:\n");
-                    for (email, count) in &patterns.emails {
-                        txt_content.push_str(&format!("  - {}: {} occurrence(s)\n", email, count));
+                    for email in &patterns.emails {
+                        txt_content.push_str(&format!("  - {}: {}\n", email.value, email.summary()));
                     }
                 }
 
                 if !patterns.usernames.is_empty() {
                     txt_content.push_str("\nUsernames/Social Media Found:\n");
-                    for (username, count) in &patterns.usernames {
-                        txt_content.push_str(&format!("  - @{}: {} occurrence(s)\n", username, count));
+                    for username in &patterns.usernames {
+                        txt_content.push_str(&format!("  - @{}: {}\n", username.value, username.summary()));
                     }
                 }
 
+                if !patterns.directory_spam.is_empty() {
+                    txt_content.push_str(&format!("\nDirectory Spam ({}):\n",
+                        if patterns.spam_included { "included in analysis" } else { "excluded from analysis" }));
+                    for entry in &patterns.directory_spam {
+                        txt_content.push_str(&format!("  - [{}] {} ({})\n", entry.source, entry.title, entry.reason));
+                        if !entry.url.is_empty() {
+                            txt_content.push_str(&format!("      {}\n", entry.url));
+                        }
+                    }
+                }
+
+                if !people_records.is_empty() {
+                    txt_content.push_str("\nPeople Search Records:\n");
+                    txt_content.push_str(&person::format_table(&people_records));
+                }
+
                 txt_content.push_str(&format!("\n{}\n", "=".repeat(60)));
                 txt_content.push_str("\nDetailed Results:\n\n");
 
-                for (format, results) in &all_results {
-                    txt_content.push_str(&format!("Format: {}\n", format));
+                for (query, results) in &all_results {
+                    txt_content.push_str(&format!("Query: {}\n", query));
                     for result in results {
-                        txt_content.push_str(&format!("  [{}] {}\n", result.source, result.title));
+                        match result.match_mode {
+                            Some(mode) => txt_content.push_str(&format!("  [{}/{}] {}\n", result.source, mode.as_str(), result.title)),
+                            None => txt_content.push_str(&format!("  [{}] {}\n", result.source, result.title)),
+                        }
                         if !result.snippet.is_empty() {
                             txt_content.push_str(&format!("       {}\n", result.snippet));
                         }
+                        if !result.url.is_empty() {
+                            txt_content.push_str(&format!("       {}\n", result.url));
+                        }
                     }
                     txt_content.push('\n');
                 }
 
-                fs::write(&filename, txt_content)?;
+                txt_content
             }
+        };
+        let mut saved_files = vec![crypt::write_file(&filename, contents.as_bytes(), encryption.as_ref())?];
+        for (path, contents) in &extra_files {
+            saved_files.push(crypt::write_file(path, contents.as_bytes(), encryption.as_ref())?);
+        }
+
+        // Evidence manifest with a hash of what was saved
+        let mut manifest = Manifest::new(&case, &phone_number);
+        for path in &saved_files {
+            manifest.add_file(path)?;
+        }
+        saved_files.push(manifest.write(&manifest_path, encryption.as_ref())?);
+
+        // Referenced from the audit log so retention can find them later
+        for path in &saved_files {
+            let path = fs::canonicalize(path).unwrap_or_else(|_| path.clone());
+            record.entry.files.push(path.display().to_string());
         }
 
+        // Everything but the manifest
+        let results_saved: Vec<String> = saved_files[..saved_files.len() - 1].iter().map(|p| p.display().to_string()).collect();
         qprint!(args.quiet, args.no_color,
-            format!("Results saved to: {}\n", filename).green(),
-            format!("Results saved to: {}\n", filename));
+            format!("Results saved to: {}\n", results_saved.join(", ")).green(),
+            format!("Results saved to: {}\n", results_saved.join(", ")));
     }
 
     // OSINT Tool Integration
@@ -990,7 +1541,7 @@ async fn main() -> anyhow::Result<()> {
             };
 
             if run_sherlock_tool {
-                let _ = run_sherlock(&usernames, args.no_color);
+                let _ = run_sherlock(&usernames, &redactor, args.no_color);
             }
         }
 
@@ -1009,7 +1560,7 @@ async fn main() -> anyhow::Result<()> {
             };
 
             if run_blackbird_tool {
-                let _ = run_blackbird(&emails, args.no_color);
+                let _ = run_blackbird(&emails, &redactor, args.no_color);
             }
 
             // email2phonenumber integration
@@ -1022,7 +1573,7 @@ async fn main() -> anyhow::Result<()> {
             };
 
             if run_e2p {
-                let _ = run_email2phone(&emails, args.no_color);
+                let _ = run_email2phone(&emails, &redactor, args.no_color);
             }
         }
     }
@@ -0,0 +1,357 @@
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+/// Name candidates scoring below this are rejected by `extract_names`
+pub const MIN_NAME_LIKELIHOOD: f64 = 0.45;
+
+/// Frequency (per 100,000 people) at which a name scores the maximum
+const MAX_FREQUENCY: f64 = 2000.0;
+
+/// Given names are more telling than surnames, which have a long tail
+const FIRST_NAME_WEIGHT: f64 = 0.6;
+const SURNAME_WEIGHT: f64 = 0.4;
+
+/// Scores for words missing from the dictionaries
+const UNKNOWN_FIRST_NAME: f64 = 0.1;
+const UNKNOWN_SURNAME: f64 = 0.25;
+
+/// Lowercase surname particles ("Maria de la Cruz", "Anna van der Berg")
+pub const PARTICLES: &[&str] = &[
+    "de", "del", "della", "di", "da", "dos", "das", "du", "la", "le", "van", "von", "der", "den",
+    "ter", "ten", "bin", "ibn", "al",
+];
+
+/// Generational suffixes in canonical form
+const SUFFIXES: &[&str] = &["Jr.", "Sr.", "II", "III", "IV"];
+
+/// Shortest names compared by edit distance; shorter ones must match exactly
+const MIN_FUZZY_LENGTH: usize = 5;
+
+lazy_static! {
+    static ref FIRST_NAMES: HashMap<String, u32> = load(include_str!("data/first_names.txt"));
+    static ref SURNAMES: HashMap<String, u32> = load(include_str!("data/surnames.txt"));
+    /// Nickname -> the formal names it can stand for
+    static ref NICKNAMES: HashMap<String, Vec<String>> = load_nicknames(include_str!("data/nicknames.txt"));
+}
+
+/// A name found in text
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameMatch {
+    /// Canonical display form (see `canonical`)
+    pub name: String,
+    /// The text as written, e.g. "SMITH, JOHN"
+    pub matched: String,
+    pub likelihood: f64,
+}
+
+/// Parse `<name> <frequency>` lines, skipping comments
+fn load(list: &str) -> HashMap<String, u32> {
+    list.lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .filter_map(|line| {
+            let (name, frequency) = line.trim().rsplit_once(' ')?;
+            Some((key(name), frequency.parse().ok()?))
+        })
+        .collect()
+}
+
+/// Parse `<name> <nickname>...` lines into nickname -> formal names
+fn load_nicknames(list: &str) -> HashMap<String, Vec<String>> {
+    let mut nicknames: HashMap<String, Vec<String>> = HashMap::new();
+    for line in list.lines().filter(|line| !line.trim_start().starts_with('#')) {
+        let mut words = line.split_whitespace().map(key);
+        let Some(formal) = words.next() else {
+            continue;
+        };
+        for nickname in words {
+            nicknames.entry(nickname).or_default().push(formal.clone());
+        }
+    }
+    nicknames
+}
+
+/// Lookup key: letters only, lowercased and without accents, so "O'Brien"
+/// matches "OBRIEN" and "García" matches "Garcia"
+pub fn key(word: &str) -> String {
+    word.chars()
+        .filter(|c| c.is_alphabetic())
+        .flat_map(char::to_lowercase)
+        .map(fold_accent)
+        .collect()
+}
+
+/// Strip the accent from common Latin letters
+fn fold_accent(c: char) -> char {
+    match c {
+        'á' | 'à' | 'â' | 'ä' | 'ã' | 'å' => 'a',
+        'é' | 'è' | 'ê' | 'ë' => 'e',
+        'í' | 'ì' | 'î' | 'ï' => 'i',
+        'ó' | 'ò' | 'ô' | 'ö' | 'õ' | 'ø' => 'o',
+        'ú' | 'ù' | 'û' | 'ü' => 'u',
+        'ñ' => 'n',
+        'ç' => 'c',
+        'ý' | 'ÿ' => 'y',
+        _ => c,
+    }
+}
+
+/// 0.6-1.0 for listed names, scaled by log frequency
+fn word_score(list: &HashMap<String, u32>, word: &str, unknown: f64) -> f64 {
+    match list.get(&key(word)) {
+        Some(&frequency) => {
+            let scaled = (f64::from(frequency) + 1.0).ln() / (MAX_FREQUENCY + 1.0).ln();
+            0.6 + 0.4 * scaled.min(1.0)
+        }
+        None => unknown,
+    }
+}
+
+/// Hyphenated surnames score as their best-known part
+fn surname_score(word: &str) -> f64 {
+    word.split('-')
+        .chain(std::iter::once(word))
+        .map(|part| word_score(&SURNAMES, part, UNKNOWN_SURNAME))
+        .fold(UNKNOWN_SURNAME, f64::max)
+}
+
+pub fn is_suffix(token: &str) -> bool {
+    SUFFIXES.contains(&canonical_suffix(token).as_str())
+}
+
+pub fn is_initial(token: &str) -> bool {
+    let letters: Vec<char> = token.chars().filter(|c| c.is_alphabetic()).collect();
+    letters.len() == 1 && token.chars().all(|c| c.is_alphabetic() || c == '.')
+}
+
+pub fn is_particle(token: &str) -> bool {
+    PARTICLES.contains(&token.to_lowercase().as_str())
+}
+
+fn canonical_suffix(token: &str) -> String {
+    match token.trim_end_matches('.').to_lowercase().as_str() {
+        "jr" => "Jr.".to_string(),
+        "sr" => "Sr.".to_string(),
+        other => other.to_uppercase(),
+    }
+}
+
+/// One word in display case: mixed-case words are kept as written, ALL CAPS
+/// and lowercase words become "Smith", "O'Brien", "McDonald", "García-López"
+pub fn canonical_word(word: &str) -> String {
+    if word.chars().any(|c| c.is_lowercase()) && word.chars().any(|c| c.is_uppercase()) {
+        return word.to_string();
+    }
+    word.split('-')
+        .map(|part| {
+            let lower = part.to_lowercase();
+            let mut out = String::new();
+            let mut capitalize = true;
+            for (i, c) in lower.chars().enumerate() {
+                if capitalize {
+                    out.extend(c.to_uppercase());
+                } else {
+                    out.push(c);
+                }
+                // O'Brien, D'Angelo, McDonald
+                capitalize = i == 1 && (c == '\'' || c == '’' || lower.starts_with("mc"));
+            }
+            out
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// One display form for a name however it was written: "SMITH, JOHN A" and
+/// "John A Smith" both become "John A. Smith"; particles are lowercased,
+/// initials get a period and suffixes are normalized ("JR" -> "Jr.")
+pub fn canonical(name: &str) -> String {
+    let name = name.trim();
+    let mut tokens: Vec<&str> = Vec::new();
+    let mut suffixes: Vec<String> = Vec::new();
+
+    // "Smith, John" is reordered; "John Smith, Jr." only loses its comma
+    let ordered: Vec<&str> = match name.split_once(',') {
+        Some((before, after)) if !after.split_whitespace().all(is_suffix) => {
+            after.split_whitespace().chain(before.split_whitespace()).collect()
+        }
+        Some((before, after)) => before.split_whitespace().chain(after.split_whitespace()).collect(),
+        None => name.split_whitespace().collect(),
+    };
+    for token in ordered {
+        let token = token.trim_matches(',');
+        if token.is_empty() {
+            continue;
+        }
+        if !tokens.is_empty() && is_suffix(token) {
+            suffixes.push(canonical_suffix(token));
+        } else {
+            tokens.push(token);
+        }
+    }
+
+    let last = tokens.len().saturating_sub(1);
+    let mut words: Vec<String> = tokens
+        .iter()
+        .enumerate()
+        .map(|(i, token)| {
+            if is_initial(token) {
+                format!("{}.", token.trim_end_matches('.').to_uppercase())
+            } else if i > 0 && i < last && is_particle(token) {
+                token.to_lowercase()
+            } else {
+                canonical_word(token)
+            }
+        })
+        .collect();
+    words.extend(suffixes);
+    words.join(" ")
+}
+
+/// The name itself plus every formal name it is a nickname for
+fn given_forms(name: &str) -> Vec<String> {
+    let name = key(name);
+    let mut forms = NICKNAMES.get(&name).cloned().unwrap_or_default();
+    forms.push(name);
+    forms
+}
+
+/// Close enough to be a misspelling: one edit apart, for longer names
+fn near_spelling(a: &str, b: &str) -> bool {
+    let (a, b) = (key(a), key(b));
+    a == b
+        || (a.chars().count().min(b.chars().count()) >= MIN_FUZZY_LENGTH
+            && strsim::damerau_levenshtein(&a, &b) <= 1)
+}
+
+/// Whether two given names can belong to the same person: same name,
+/// nicknames of the same name (Bob/Robert, Bob/Rob), an initial that fits
+/// (J./John) or a one-letter misspelling (Michael/Micheal)
+pub fn same_given_name(a: &str, b: &str) -> bool {
+    if is_initial(a) || is_initial(b) {
+        // "R." fits Bob through Robert
+        let (a, b) = if is_initial(a) { (a, b) } else { (b, a) };
+        let letter = key(a).chars().next();
+        return given_forms(b).iter().any(|form| form.chars().next() == letter);
+    }
+    if near_spelling(a, b) {
+        return true;
+    }
+    let forms = given_forms(b);
+    given_forms(a).iter().any(|form| forms.contains(form))
+}
+
+/// Whether two surnames can be the same: equal, one edit apart, or one
+/// part of the other's hyphenated form (García / García-López)
+pub fn same_surname(a: &str, b: &str) -> bool {
+    near_spelling(a, b)
+        || a.split('-').any(|part| near_spelling(part, b))
+        || b.split('-').any(|part| near_spelling(part, a))
+}
+
+/// How likely a name is a person's name (0-1), from how common its first
+/// word is as a given name and its last word (ignoring suffixes) as a surname
+pub fn likelihood(name: &str) -> f64 {
+    let words: Vec<&str> = name.split_whitespace().filter(|w| !is_suffix(w)).collect();
+    let (Some(first), Some(last)) = (words.first(), words.last()) else {
+        return 0.0;
+    };
+    if words.len() < 2 {
+        return 0.0;
+    }
+    let first_score = if is_initial(first) {
+        UNKNOWN_FIRST_NAME
+    } else {
+        word_score(&FIRST_NAMES, first, UNKNOWN_FIRST_NAME)
+    };
+    let score = FIRST_NAME_WEIGHT * first_score + SURNAME_WEIGHT * surname_score(last);
+    (score * 1000.0).round() / 1000.0
+}
+
+/// The most likely name within a candidate: the whole candidate or, when a
+/// plain word may have been caught at either end, the candidate without it
+/// (e.g. "Call John Smith" -> "John Smith"). Returns None when nothing
+/// reaches `MIN_NAME_LIKELIHOOD`.
+pub fn best_name(candidate: &str) -> Option<NameMatch> {
+    let name = canonical(candidate);
+    let words: Vec<&str> = name.split_whitespace().collect();
+    let core = words.iter().take_while(|w| !is_suffix(w)).count();
+    let plain = |w: &str| !is_initial(w) && !is_particle(w);
+
+    let mut options = vec![name.clone()];
+    if core >= 3 {
+        if plain(words[0]) && plain(words[1]) {
+            options.push(words[1..].join(" "));
+        }
+        if plain(words[core - 1]) && plain(words[core - 2]) {
+            options.push(words[..core - 1].join(" "));
+        }
+    }
+
+    let mut best: Option<(String, f64)> = None;
+    for option in options {
+        let score = likelihood(&option);
+        if best.as_ref().is_none_or(|(_, b)| score > *b) {
+            best = Some((option, score));
+        }
+    }
+    best.filter(|(_, score)| *score >= MIN_NAME_LIKELIHOOD)
+        .map(|(name, likelihood)| NameMatch {
+            name,
+            matched: candidate.to_string(),
+            likelihood,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn best(candidate: &str) -> Option<String> {
+        best_name(candidate).map(|m| m.name)
+    }
+
+    #[test]
+    fn test_likelihood_ranks_real_names() {
+        assert!(likelihood("John Smith") > 0.9);
+        assert!(likelihood("Jane Doe") >= MIN_NAME_LIKELIHOOD);
+        assert!(likelihood("Patrick O'Brien") > likelihood("Patrick Zyx"));
+        assert!(likelihood("Maria García-López") > 0.8);
+        assert!(likelihood("Best Price") < MIN_NAME_LIKELIHOOD);
+        assert!(likelihood("Customer Reviews") < MIN_NAME_LIKELIHOOD);
+        assert_eq!(likelihood("John"), 0.0);
+    }
+
+    #[test]
+    fn test_best_name_trims_stray_words() {
+        assert_eq!(best("Call John Smith").as_deref(), Some("John Smith"));
+        assert_eq!(best("John Smith Philadelphia").as_deref(), Some("John Smith"));
+        assert_eq!(best("John Michael Smith").as_deref(), Some("John Michael Smith"));
+        assert_eq!(best("North Carolina"), None);
+    }
+
+    #[test]
+    fn test_name_variants_match() {
+        assert!(same_given_name("Bob", "Robert"));
+        assert!(same_given_name("Bob", "Rob"));
+        assert!(same_given_name("J.", "John"));
+        assert!(same_given_name("Michael", "Micheal"));
+        assert!(!same_given_name("John", "Jane"));
+        assert!(!same_given_name("Jon", "Jan"));
+        assert!(same_surname("García-López", "Garcia"));
+        assert!(same_surname("Thompson", "Thomson"));
+        assert!(!same_surname("Smith", "Jones"));
+        assert!(!same_surname("Lee", "Lea"));
+    }
+
+    #[test]
+    fn test_canonical_forms() {
+        assert_eq!(canonical("SMITH, JOHN A"), "John A. Smith");
+        assert_eq!(canonical("john a. smith"), "John A. Smith");
+        assert_eq!(canonical("JOHN SMITH JR"), "John Smith Jr.");
+        assert_eq!(canonical("Robert Smith, Jr."), "Robert Smith Jr.");
+        assert_eq!(canonical("PATRICK O'BRIEN"), "Patrick O'Brien");
+        assert_eq!(canonical("RONALD MCDONALD III"), "Ronald McDonald III");
+        assert_eq!(canonical("MARÍA GARCÍA-LÓPEZ"), "María García-López");
+        assert_eq!(canonical("Maria De La Cruz"), "Maria de la Cruz");
+    }
+}
@@ -1,16 +1,96 @@
+use crate::exclusion::Exclusions;
+use crate::names::{best_name, NameMatch, PARTICLES};
+use clap::ValueEnum;
 use lazy_static::lazy_static;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
+/// Kinds of data that can be collected: entities the extractors pull from
+/// result text, plus fields only people search records carry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntityKind {
+    Names,
+    Locations,
+    Emails,
+    Usernames,
+    /// Age ranges on people search records
+    Ages,
+    /// Associated numbers, carrier and line type on people search records
+    Phones,
+}
+
+impl EntityKind {
+    pub const ALL: [EntityKind; 6] = [
+        EntityKind::Names,
+        EntityKind::Locations,
+        EntityKind::Emails,
+        EntityKind::Usernames,
+        EntityKind::Ages,
+        EntityKind::Phones,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntityKind::Names => "names",
+            EntityKind::Locations => "locations",
+            EntityKind::Emails => "emails",
+            EntityKind::Usernames => "usernames",
+            EntityKind::Ages => "ages",
+            EntityKind::Phones => "phones",
+        }
+    }
+}
+
+/// A capitalized or ALL CAPS name word, optionally hyphenated:
+/// "Smith", "O'Brien", "McDonald", "García-López", "SMITH"
+const NAME_WORD: &str = r"(?:\p{Lu}(?:['’]\p{Lu})?\p{Ll}+(?:\p{Lu}\p{Ll}+)?|\p{Lu}(?:['’]?\p{Lu})+)(?:-(?:\p{Lu}(?:['’]\p{Lu})?\p{Ll}+(?:\p{Lu}\p{Ll}+)?|\p{Lu}(?:['’]?\p{Lu})+))*";
+
+/// A middle initial: "A." or "A"
+const NAME_INITIAL: &str = r"\p{Lu}\b\.?";
+
+/// "Jr.", ", Sr", "III"
+const NAME_SUFFIX: &str = r"(?:,?\s+(?:Jr|JR|Sr|SR)\b\.?|\s+(?:II|III|IV)\b)?";
+
+/// Lowercase or ALL CAPS particles before a surname: "de la", "van der"
+fn particles_pattern() -> String {
+    let alternatives: Vec<String> = PARTICLES
+        .iter()
+        .flat_map(|p| [p.to_string(), p.to_uppercase()])
+        .collect();
+    format!(r"(?:\s+(?:{}))*", alternatives.join("|"))
+}
+
 lazy_static! {
-    static ref NAME_PATTERN: Regex = Regex::new(r"\b([A-Z][a-z]+(?:\s+[A-Z][a-z]+){1,2})\b").unwrap();
+    /// "John A. Smith Jr.", "Maria de la Cruz", "PATRICK O'BRIEN"
+    static ref NAME_PATTERN: Regex = Regex::new(&format!(
+        r"\b{word}(?:\s+(?:{word}|{initial}))?{particles}\s+{word}{suffix}",
+        word = NAME_WORD,
+        initial = NAME_INITIAL,
+        particles = particles_pattern(),
+        suffix = NAME_SUFFIX,
+    )).unwrap();
+    /// "SMITH, JOHN A", "Smith, John"
+    static ref REVERSED_NAME_PATTERN: Regex = Regex::new(&format!(
+        r"\b{word},\s+{word}(?:\s+(?:{word}|{initial}))?{suffix}",
+        word = NAME_WORD,
+        initial = NAME_INITIAL,
+        suffix = NAME_SUFFIX,
+    )).unwrap();
     static ref STATE_PATTERN: Regex = Regex::new(
         r"\b(AL|AK|AZ|AR|CA|CO|CT|DC|DE|FL|GA|HI|ID|IL|IN|IA|KS|KY|LA|ME|MD|MA|MI|MN|MS|MO|MT|NE|NV|NH|NJ|NM|NY|NC|ND|OH|OK|OR|PA|RI|SC|SD|TN|TX|UT|VT|VA|WA|WV|WI|WY)\b"
     ).unwrap();
-    static ref CITY_STATE_PATTERN: Regex = Regex::new(
-        r"\b([A-Z][a-z]+(?:\s+[A-Z][a-z]+)?),?\s+(AL|AK|AZ|AR|CA|CO|CT|DC|DE|FL|GA|HI|ID|IL|IN|IA|KS|KY|LA|ME|MD|MA|MI|MN|MS|MO|MT|NE|NV|NH|NJ|NM|NY|NC|ND|OH|OK|OR|PA|RI|SC|SD|TN|TX|UT|VT|VA|WA|WV|WI|WY)\b"
-    ).unwrap();
+    /// "City, ST" for states and Canadian provinces
+    static ref CITY_STATE_PATTERN: Regex = Regex::new(&format!(
+        r"\b([A-Z][a-z]+(?:\s+[A-Z][a-z]+)?),?\s+({})\b",
+        US_STATES.iter().chain(CA_PROVINCES.iter()).map(|(code, _)| *code).collect::<Vec<_>>().join("|")
+    )).unwrap();
     static ref ZIP_PATTERN: Regex = Regex::new(r"\b\d{5}(?:-\d{4})?\b").unwrap();
+    /// Canadian postal code ("M5V 2T6", "M5V2T6"); D, F, I, O, Q and U are never used
+    static ref POSTAL_CODE_PATTERN: Regex = Regex::new(
+        r"\b([ABCEGHJ-NPRSTVXY]\d[ABCEGHJ-NPRSTV-Z])[ -]?(\d[ABCEGHJ-NPRSTV-Z]\d)\b"
+    ).unwrap();
 
     // Email pattern
     static ref EMAIL_PATTERN: Regex = Regex::new(
@@ -26,55 +106,8 @@ lazy_static! {
     static ref SOCIAL_URL_PATTERN: Regex = Regex::new(
         r"(?i)(?:facebook\.com|fb\.com|twitter\.com|x\.com|instagram\.com|linkedin\.com|tiktok\.com|snapchat\.com|youtube\.com|pinterest\.com)/(?:@)?([a-zA-Z0-9._-]+)"
     ).unwrap();
-    
-    static ref EXCLUDED_WORDS: HashSet<&'static str> = {
-        let mut set = HashSet::new();
-        set.insert("Phone");
-        set.insert("Number");
-        set.insert("Call");
-        set.insert("Contact");
-        set.insert("Email");
-        set.insert("Address");
-        set.insert("Street");
-        set.insert("City");
-        set.insert("State");
-        set.insert("Country");
-        set.insert("The");
-        set.insert("This");
-        set.insert("That");
-        set.insert("Search");
-        set.insert("Results");
-        set.insert("View");
-        set.insert("More");
-        set.insert("Less");
-        set.insert("Show");
-        set.insert("Hide");
-        set.insert("United States");
-        set.insert("New York");
-        set.insert("Los Angeles");
-        set.insert("San Francisco");
-        set.insert("Google");
-        set.insert("Bing");
-        set.insert("Yahoo");
-        set.insert("Facebook");
-        set.insert("Twitter");
-        set.insert("Instagram");
-        set.insert("Best");
-        set.insert("Top");
-        set.insert("Free");
-        set.insert("Online");
-        set.insert("Reviews");
-        set.insert("About");
-        set.insert("Home");
-        set.insert("Business");
-        set.insert("Service");
-        set.insert("Services");
-        set.insert("Company");
-        set.insert("Companies");
-        set
-    };
-
-    static ref US_STATES: Vec<(&'static str, &'static str)> = vec![
+
+    pub static ref US_STATES: Vec<(&'static str, &'static str)> = vec![
         ("AL", "Alabama"),
         ("AK", "Alaska"),
         ("AZ", "Arizona"),
@@ -127,25 +160,114 @@ lazy_static! {
         ("WI", "Wisconsin"),
         ("WY", "Wyoming"),
     ];
+
+    pub static ref CA_PROVINCES: Vec<(&'static str, &'static str)> = vec![
+        ("AB", "Alberta"),
+        ("BC", "British Columbia"),
+        ("MB", "Manitoba"),
+        ("NB", "New Brunswick"),
+        ("NL", "Newfoundland and Labrador"),
+        ("NS", "Nova Scotia"),
+        ("NT", "Northwest Territories"),
+        ("NU", "Nunavut"),
+        ("ON", "Ontario"),
+        ("PE", "Prince Edward Island"),
+        ("QC", "Quebec"),
+        ("SK", "Saskatchewan"),
+        ("YT", "Yukon"),
+    ];
+
+    /// Countries outside the US and Canada that share the +1 country code
+    pub static ref NANP_COUNTRIES: Vec<&'static str> = vec![
+        "Anguilla",
+        "Antigua and Barbuda",
+        "Bahamas",
+        "Barbados",
+        "Bermuda",
+        "British Virgin Islands",
+        "Cayman Islands",
+        "Dominica",
+        "Dominican Republic",
+        "Grenada",
+        "Jamaica",
+        "Montserrat",
+        "Saint Kitts and Nevis",
+        "Saint Lucia",
+        "Saint Vincent and the Grenadines",
+        "Sint Maarten",
+        "Trinidad and Tobago",
+        "Turks and Caicos Islands",
+    ];
+}
+
+/// The two-letter code for a state or province written as its code ("PA",
+/// capitals only) or its name in any case ("Pennsylvania", "NEW YORK")
+pub fn state_code(text: &str) -> Option<&'static str> {
+    let text = if text.eq_ignore_ascii_case("Québec") { "Quebec" } else { text };
+    US_STATES
+        .iter()
+        .chain(CA_PROVINCES.iter())
+        .find(|(code, name)| *code == text || name.eq_ignore_ascii_case(text))
+        .map(|(code, _)| *code)
 }
 
-pub fn extract_names(text: &str) -> Vec<String> {
+/// The full name for a state or province code
+pub fn state_name(code: &str) -> Option<&'static str> {
+    US_STATES
+        .iter()
+        .chain(CA_PROVINCES.iter())
+        .find(|(c, _)| *c == code)
+        .map(|(_, name)| *name)
+}
+
+/// A Caribbean or Atlantic NANP country by name, in any case
+pub fn nanp_country(text: &str) -> Option<&'static str> {
+    NANP_COUNTRIES.iter().find(|name| name.eq_ignore_ascii_case(text)).copied()
+}
+
+/// Extract likely person names in canonical form, each with its likelihood
+/// (see `names::likelihood`). Handles apostrophes, Mc/Mac, accents, hyphens,
+/// particles, initials, suffixes, ALL CAPS and "SMITH, JOHN" ordering.
+/// Candidates the name dictionaries make implausible are dropped.
+pub fn extract_names(text: &str, exclusions: &Exclusions) -> Vec<NameMatch> {
     let mut names = Vec::new();
+    let mut taken: Vec<(usize, usize)> = Vec::new();
 
-    for cap in NAME_PATTERN.captures_iter(text) {
-        if let Some(name_match) = cap.get(1) {
-            let name = name_match.as_str();
-            
-            // Filter out excluded words and single-word names
-            if !EXCLUDED_WORDS.contains(name) && name.split_whitespace().count() >= 2 {
-                names.push(name.to_string());
-            }
+    // "Surname, Given" only when it doesn't follow another name word, so a
+    // list like "John Smith, Jane Doe" isn't read as "Jane Doe Smith"
+    for found in REVERSED_NAME_PATTERN.find_iter(text) {
+        let before = text[..found.start()].trim_end();
+        let previous = before.rsplit(char::is_whitespace).next().unwrap_or("");
+        let after_word = previous.chars().next().is_some_and(char::is_uppercase)
+            && previous.chars().all(|c| c.is_alphabetic() || "'’-.".contains(c));
+        if after_word {
+            continue;
+        }
+        if let Some(name) = accept_name(found.as_str(), exclusions) {
+            taken.push((found.start(), found.end()));
+            names.push(name);
+        }
+    }
+
+    for found in NAME_PATTERN.find_iter(text) {
+        if taken.iter().any(|&(start, end)| found.start() < end && start < found.end()) {
+            continue;
+        }
+        if let Some(name) = accept_name(found.as_str(), exclusions) {
+            names.push(name);
         }
     }
 
     names
 }
 
+fn accept_name(candidate: &str, exclusions: &Exclusions) -> Option<NameMatch> {
+    best_name(candidate).filter(|m| !exclusions.excludes_name(&m.name))
+}
+
+/// Extract states, "City, ST" pairs, ZIPs, Canadian provinces and postal
+/// codes, and Caribbean NANP countries. Province codes are only taken after
+/// a city, since bare "ON" or "NS" is usually just a word.
 pub fn extract_locations(text: &str) -> Vec<String> {
     let mut locations = Vec::new();
 
@@ -163,12 +285,22 @@ pub fn extract_locations(text: &str) -> Vec<String> {
         }
     }
 
-    // Extract full state names
-    for (_, full_name) in US_STATES.iter() {
+    // Extract full state and province names
+    for (_, full_name) in US_STATES.iter().chain(CA_PROVINCES.iter()) {
         if text.contains(full_name) {
             locations.push(full_name.to_string());
         }
     }
+    if text.contains("Québec") {
+        locations.push("Quebec".to_string());
+    }
+
+    // Extract Caribbean NANP countries
+    for country in NANP_COUNTRIES.iter() {
+        if text.contains(country) {
+            locations.push(country.to_string());
+        }
+    }
 
     // Extract zip codes
     for cap in ZIP_PATTERN.captures_iter(text) {
@@ -177,25 +309,24 @@ pub fn extract_locations(text: &str) -> Vec<String> {
         }
     }
 
+    // Extract Canadian postal codes as "A1A 1A1"
+    for cap in POSTAL_CODE_PATTERN.captures_iter(text) {
+        locations.push(format!("{} {}", &cap[1], &cap[2]));
+    }
+
     // Deduplicate
     let unique_locations: HashSet<String> = locations.into_iter().collect();
     unique_locations.into_iter().collect()
 }
 
-/// Extract email addresses from text
-pub fn extract_emails(text: &str) -> Vec<String> {
+/// Extract email addresses from text, minus excluded placeholders and senders
+pub fn extract_emails(text: &str, exclusions: &Exclusions) -> Vec<String> {
     let mut emails = Vec::new();
 
     for cap in EMAIL_PATTERN.captures_iter(text) {
         if let Some(email) = cap.get(1) {
             let email_str = email.as_str().to_lowercase();
-            // Filter out common false positives
-            if !email_str.contains("example.com")
-                && !email_str.contains("domain.com")
-                && !email_str.contains("email.com")
-                && !email_str.starts_with("noreply")
-                && !email_str.starts_with("no-reply")
-            {
+            if !exclusions.excludes_email(&email_str) {
                 emails.push(email_str);
             }
         }
@@ -206,8 +337,8 @@ pub fn extract_emails(text: &str) -> Vec<String> {
     unique.into_iter().collect()
 }
 
-/// Extract social media usernames from text
-pub fn extract_usernames(text: &str) -> Vec<String> {
+/// Extract social media usernames from text, minus excluded path segments
+pub fn extract_usernames(text: &str, exclusions: &Exclusions) -> Vec<String> {
     let mut usernames = Vec::new();
 
     // Extract @username mentions
@@ -216,7 +347,7 @@ pub fn extract_usernames(text: &str) -> Vec<String> {
             let uname = username.as_str().to_lowercase();
             // Filter out common false positives
             if uname.len() > 2
-                && !EXCLUDED_USERNAMES.contains(&uname.as_str())
+                && !exclusions.excludes_username(&uname)
             {
                 usernames.push(uname);
             }
@@ -230,7 +361,7 @@ pub fn extract_usernames(text: &str) -> Vec<String> {
             // Filter out path segments that aren't usernames
             if uname.len() > 2
                 && !uname.contains('.')
-                && !EXCLUDED_USERNAMES.contains(&uname.as_str())
+                && !exclusions.excludes_username(&uname)
             {
                 usernames.push(uname);
             }
@@ -242,17 +373,6 @@ pub fn extract_usernames(text: &str) -> Vec<String> {
     unique.into_iter().collect()
 }
 
-/// Common usernames to exclude (generic paths/words)
-static EXCLUDED_USERNAMES: &[&str] = &[
-    "home", "about", "contact", "help", "support", "privacy", "terms",
-    "login", "signup", "register", "settings", "profile", "search",
-    "share", "explore", "discover", "trending", "popular", "new",
-    "user", "users", "page", "pages", "group", "groups", "event", "events",
-    "photo", "photos", "video", "videos", "story", "stories", "reel", "reels",
-    "post", "posts", "comment", "comments", "like", "likes", "follow", "following",
-    "followers", "friends", "messages", "notifications", "watch", "shop",
-    "marketplace", "gaming", "live", "news", "feed", "hashtag", "tag",
-];
 
 #[cfg(test)]
 mod tests {
@@ -262,11 +382,33 @@ mod tests {
     fn test_extract_names() {
         // Names need to be in context where they're isolated as 2-word phrases
         let text = "The owner is John Smith and his wife is Jane Doe.";
-        let names = extract_names(text);
+        let names: Vec<String> = extract_names(text, &Exclusions::default()).into_iter().map(|m| m.name).collect();
         assert!(names.contains(&"John Smith".to_string()));
         assert!(names.contains(&"Jane Doe".to_string()));
     }
 
+    #[test]
+    fn test_extract_names_rejects_unlikely_candidates() {
+        let names = extract_names("Best Price Deals from Call John Smith", &Exclusions::default());
+        assert_eq!(names.len(), 1);
+        assert_eq!(names[0].name, "John Smith");
+        assert!(names[0].likelihood > 0.9);
+    }
+
+    #[test]
+    fn test_extract_name_variants() {
+        let names = |text: &str| -> Vec<String> { extract_names(text, &Exclusions::default()).into_iter().map(|m| m.name).collect() };
+        assert_eq!(names("Owner: Patrick O'Brien of Boston"), vec!["Patrick O'Brien"]);
+        assert_eq!(names("Call Ronald McDonald today"), vec!["Ronald McDonald"]);
+        assert_eq!(names("Dra. María García-López, MD"), vec!["María García-López"]);
+        assert_eq!(names("Tenant Maria de la Cruz"), vec!["Maria de la Cruz"]);
+        assert_eq!(names("Listed to John A. Smith Jr. since 2010"), vec!["John A. Smith Jr."]);
+        assert_eq!(names("Robert Smith III, owner"), vec!["Robert Smith III"]);
+        assert_eq!(names("RESIDENT: JOHN SMITH"), vec!["John Smith"]);
+        assert_eq!(names("Owner: SMITH, JOHN A"), vec!["John A. Smith"]);
+        assert_eq!(names("John Smith, Jane Doe"), vec!["John Smith", "Jane Doe"]);
+    }
+
     #[test]
     fn test_extract_locations() {
         let text = "Located in Philadelphia, PA 19102";
@@ -274,6 +416,13 @@ mod tests {
         assert!(locations.contains(&"PA".to_string()));
         assert!(locations.contains(&"Philadelphia, PA".to_string()));
         assert!(locations.contains(&"19102".to_string()));
+
+        let locations = extract_locations("Toronto, ON m5v2t6 · ON HOLD · Kingston, Jamaica · Québec");
+        assert!(locations.contains(&"Toronto, ON".to_string()));
+        assert!(locations.contains(&"Jamaica".to_string()));
+        assert!(locations.contains(&"Quebec".to_string()));
+        assert!(!locations.contains(&"ON".to_string()));
+        assert!(extract_locations("Postal code M5V 2T6").contains(&"M5V 2T6".to_string()));
     }
 
     #[test]
@@ -281,8 +430,8 @@ mod tests {
         // Single capitalized words like "Phone" won't match (pattern needs 2+ words)
         // Multi-word excluded phrases like "United States" or "New York" should be filtered
         let text = "He lives in New York and United States";
-        let names = extract_names(text);
-        // New York and United States are in EXCLUDED_WORDS
+        let names: Vec<String> = extract_names(text, &Exclusions::default()).into_iter().map(|m| m.name).collect();
+        // New York and United States are on the built-in word list
         assert!(!names.contains(&"New York".to_string()));
         assert!(!names.contains(&"United States".to_string()));
     }
@@ -0,0 +1,412 @@
+use crate::person::{clean_text, extract_phone_numbers, PersonRecord};
+use crate::search::{create_client_from_config, SearchConfig};
+use anyhow::{anyhow, Result};
+use scraper::{ElementRef, Html, Selector};
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+
+use crate::{fastpeoplesearch, thatsthem, truepeoplesearch, usphonebook, whitepages};
+
+/// Placeholder in `SiteSpec::url_pattern` replaced by the formatted number
+pub const PHONE_PLACEHOLDER: &str = "{phone}";
+
+/// Records taken from one page at most
+const MAX_RECORDS_PER_SITE: usize = 5;
+
+/// How a site expects the phone number in its lookup URL
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PhoneFormat {
+    /// 5555551212
+    Digits,
+    /// 555-555-1212
+    Dashed,
+}
+
+impl PhoneFormat {
+    /// Format a 10-digit (or 1 + 10-digit) number for a lookup URL
+    pub fn apply(&self, phone: &str) -> String {
+        let digits: String = phone.chars().filter(|c| c.is_ascii_digit()).collect();
+        let national = if digits.len() == 11 && digits.starts_with('1') {
+            &digits[1..]
+        } else {
+            digits.as_str()
+        };
+
+        match self {
+            PhoneFormat::Dashed if national.len() == 10 => {
+                format!("{}-{}-{}", &national[0..3], &national[3..6], &national[6..10])
+            }
+            _ => national.to_string(),
+        }
+    }
+}
+
+/// Declarative description of a people search site.
+///
+/// Selector lists are tried in order and the first usable match wins.
+/// Records are read from each element matching a `container_selectors`
+/// entry; if none yields a record, the page itself is read as a single
+/// record using `page_name_selectors`.
+pub struct SiteSpec {
+    /// Display name, also used as `PersonRecord::source`
+    pub name: &'static str,
+    /// CLI identifier (matches the `--<id>` flag)
+    pub id: &'static str,
+    /// Lookup URL containing `{phone}`
+    pub url_pattern: &'static str,
+    pub phone_format: PhoneFormat,
+    pub container_selectors: &'static [&'static str],
+    pub name_selectors: &'static [&'static str],
+    pub address_selectors: &'static [&'static str],
+    pub past_address_selectors: &'static [&'static str],
+    pub age_selectors: &'static [&'static str],
+    pub carrier_selectors: &'static [&'static str],
+    pub line_type_selectors: &'static [&'static str],
+    /// Name selectors for a page that shows a single person without cards
+    pub page_name_selectors: &'static [&'static str],
+    /// The page fallback only runs if the page mentions one of these
+    pub page_markers: &'static [&'static str],
+    /// Lowercase words that mark a heading as not being a name
+    pub name_stopwords: &'static [&'static str],
+    /// Lowercase page text meaning the site has no record for the number
+    pub no_record_markers: &'static [&'static str],
+    /// Keep a single-person page that shows an address but no name
+    pub address_only_pages: bool,
+}
+
+/// A people search site that can look up a phone number
+pub trait PeopleSearchSite: Sync {
+    fn spec(&self) -> &SiteSpec;
+
+    /// URL to fetch for a phone number
+    fn lookup_url(&self, phone: &str) -> String {
+        let spec = self.spec();
+        spec.url_pattern
+            .replace(PHONE_PLACEHOLDER, &spec.phone_format.apply(phone))
+    }
+
+    /// Parse a fetched page into person records
+    fn parse(&self, html: &str, url: &str) -> Vec<PersonRecord> {
+        parse_with_spec(self.spec(), html, url)
+    }
+}
+
+impl PeopleSearchSite for SiteSpec {
+    fn spec(&self) -> &SiteSpec {
+        self
+    }
+}
+
+/// Every supported site, in search order
+pub fn all_sites() -> Vec<&'static dyn PeopleSearchSite> {
+    vec![
+        &whitepages::SITE,
+        &truepeoplesearch::SITE,
+        &fastpeoplesearch::SITE,
+        &thatsthem::SITE,
+        &usphonebook::SITE,
+    ]
+}
+
+/// Outcome of looking a number up on one site
+#[derive(Debug)]
+pub struct SiteOutcome {
+    pub url: String,
+    pub records: Vec<PersonRecord>,
+    /// HTTP requests made, including retries
+    pub attempts: usize,
+    pub error: Option<String>,
+}
+
+/// Enforces a minimum interval between consecutive site requests
+pub struct RateLimiter {
+    interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(interval: Duration) -> Self {
+        RateLimiter {
+            interval,
+            last_request: None,
+        }
+    }
+
+    /// Wait until the interval since the previous request has passed
+    pub async fn wait(&mut self) {
+        if let Some(last) = self.last_request {
+            let elapsed = last.elapsed();
+            if elapsed < self.interval {
+                sleep(self.interval - elapsed).await;
+            }
+        }
+        self.last_request = Some(Instant::now());
+    }
+}
+
+/// Look a number up on a site, retrying failed requests with backoff.
+///
+/// The searched number is removed from each record's associated numbers.
+pub async fn lookup(
+    site: &dyn PeopleSearchSite,
+    phone: &str,
+    config: &SearchConfig,
+    retries: usize,
+    limiter: &mut RateLimiter,
+) -> SiteOutcome {
+    let spec = site.spec();
+    let url = site.lookup_url(phone);
+    let target = PhoneFormat::Digits.apply(phone);
+    let mut attempts = 0;
+    let mut last_error = None;
+
+    for attempt in 0..=retries {
+        limiter.wait().await;
+        attempts += 1;
+        match fetch(spec, &url, config).await {
+            Ok(html) => {
+                let mut records = site.parse(&html, &url);
+                for record in &mut records {
+                    record
+                        .associated_numbers
+                        .retain(|n| PhoneFormat::Digits.apply(n) != target);
+                }
+                return SiteOutcome {
+                    url,
+                    records,
+                    attempts,
+                    error: None,
+                };
+            }
+            Err(e) => {
+                last_error = Some(e.to_string());
+                if attempt < retries {
+                    sleep(Duration::from_millis(500 * (attempt as u64 + 1))).await;
+                }
+            }
+        }
+    }
+
+    SiteOutcome {
+        url,
+        records: Vec::new(),
+        attempts,
+        error: last_error,
+    }
+}
+
+async fn fetch(spec: &SiteSpec, url: &str, config: &SearchConfig) -> Result<String> {
+    let client = create_client_from_config(config);
+    let response = client
+        .get(url)
+        .header("Accept", "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8")
+        .header("Accept-Language", "en-US,en;q=0.5")
+        .header("Connection", "keep-alive")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(anyhow!("{} error: {}", spec.name, response.status()));
+    }
+
+    Ok(response.text().await?)
+}
+
+/// Parse a page using a site description
+pub fn parse_with_spec(spec: &SiteSpec, html: &str, url: &str) -> Vec<PersonRecord> {
+    let document = Html::parse_document(html);
+    let root = document.root_element();
+    let page_text = root.text().collect::<String>().to_lowercase();
+
+    if spec.no_record_markers.iter().any(|m| page_text.contains(m)) {
+        return Vec::new();
+    }
+
+    let mut records: Vec<PersonRecord> = Vec::new();
+
+    for container in spec.container_selectors {
+        let Ok(selector) = Selector::parse(container) else {
+            continue;
+        };
+        for element in root.select(&selector).take(MAX_RECORDS_PER_SITE) {
+            if let Some(name) = first_text(element, spec.name_selectors, |t| is_name(spec, t)) {
+                push_unique(&mut records, read_record(spec, element, &name, url, true));
+            }
+        }
+        if !records.is_empty() {
+            return records;
+        }
+    }
+
+    // Single-person page without result cards
+    let has_marker = spec.page_markers.is_empty()
+        || spec
+            .page_markers
+            .iter()
+            .any(|m| page_text.contains(&m.to_lowercase()));
+    if has_marker {
+        match first_text(root, spec.page_name_selectors, |t| is_name(spec, t)) {
+            Some(name) => records.push(read_record(spec, root, &name, url, false)),
+            None if spec.address_only_pages => {
+                let record = read_record(spec, root, "", url, false);
+                if record.current_address.is_some() {
+                    records.push(record);
+                }
+            }
+            None => {}
+        }
+    }
+
+    records
+}
+
+fn read_record(
+    spec: &SiteSpec,
+    element: ElementRef,
+    name: &str,
+    url: &str,
+    with_numbers: bool,
+) -> PersonRecord {
+    let mut record = PersonRecord::new(name, spec.name, url);
+    record.current_address = first_text(element, spec.address_selectors, is_address);
+    record.past_addresses = all_texts(element, spec.past_address_selectors)
+        .into_iter()
+        .filter(|a| is_address(a) && Some(a) != record.current_address.as_ref())
+        .collect();
+    record.age_range = first_text(element, spec.age_selectors, |t| !t.is_empty());
+    record.carrier = first_text(element, spec.carrier_selectors, |t| !t.is_empty());
+    record.line_type = first_text(element, spec.line_type_selectors, |t| !t.is_empty());
+    if with_numbers {
+        record.associated_numbers = extract_phone_numbers(&element.text().collect::<String>());
+    }
+    record
+}
+
+/// Cleaned text of the first element matching any selector that passes `accept`
+fn first_text(element: ElementRef, selectors: &[&str], accept: impl Fn(&str) -> bool) -> Option<String> {
+    for selector_str in selectors {
+        let Ok(selector) = Selector::parse(selector_str) else {
+            continue;
+        };
+        if let Some(found) = element.select(&selector).next() {
+            let text = clean_text(&found.text().collect::<String>());
+            if accept(&text) {
+                return Some(text);
+            }
+        }
+    }
+    None
+}
+
+/// Cleaned text of every element matching any selector
+fn all_texts(element: ElementRef, selectors: &[&str]) -> Vec<String> {
+    let mut texts: Vec<String> = Vec::new();
+    for selector_str in selectors {
+        let Ok(selector) = Selector::parse(selector_str) else {
+            continue;
+        };
+        for found in element.select(&selector) {
+            let text = clean_text(&found.text().collect::<String>());
+            if !text.is_empty() && !texts.contains(&text) {
+                texts.push(text);
+            }
+        }
+    }
+    texts
+}
+
+fn is_name(spec: &SiteSpec, text: &str) -> bool {
+    let lower = text.to_lowercase();
+    text.len() > 2
+        && text.len() < 100
+        && text.chars().filter(|c| c.is_alphabetic()).count() > 2
+        && !spec.name_stopwords.iter().any(|w| lower.contains(w))
+}
+
+fn is_address(text: &str) -> bool {
+    text.len() > 5 && (text.contains(',') || text.chars().any(|c| c.is_ascii_digit()))
+}
+
+fn push_unique(records: &mut Vec<PersonRecord>, record: PersonRecord) {
+    let duplicate = records
+        .iter()
+        .any(|r| r.full_name == record.full_name && r.current_address == record.current_address);
+    if !duplicate {
+        records.push(record);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_SITE: SiteSpec = SiteSpec {
+        name: "TestSite",
+        id: "testsite",
+        url_pattern: "https://people.example/phone/{phone}",
+        phone_format: PhoneFormat::Dashed,
+        container_selectors: &[".card"],
+        name_selectors: &[".name", "h2"],
+        address_selectors: &[".address"],
+        past_address_selectors: &[".past"],
+        age_selectors: &[".age"],
+        carrier_selectors: &[],
+        line_type_selectors: &[".type"],
+        page_name_selectors: &["h1"],
+        page_markers: &["Lives in"],
+        name_stopwords: &["search"],
+        no_record_markers: &["no records found"],
+        address_only_pages: false,
+    };
+
+    #[test]
+    fn test_phone_format() {
+        assert_eq!(PhoneFormat::Dashed.apply("15555551212"), "555-555-1212");
+        assert_eq!(PhoneFormat::Digits.apply("(555) 555-1212"), "5555551212");
+        assert_eq!(TEST_SITE.lookup_url("5555551212"), "https://people.example/phone/555-555-1212");
+    }
+
+    #[test]
+    fn test_parse_cards() {
+        let html = r#"<html><body>
+            <div class="card"><h2>Search Results</h2></div>
+            <div class="card">
+              <span class="name">John   Smith</span>
+              <span class="age">Age 40s</span>
+              <span class="address">123 Main St, Philadelphia, PA</span>
+              <span class="past">9 Elm Rd, Camden, NJ</span>
+              <span class="type">Landline</span>
+              Phones: (555) 555-1212, (555) 555-3434
+            </div>
+        </body></html>"#;
+        let records = TEST_SITE.parse(html, "https://people.example/phone/555-555-1212");
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record.full_name, "John Smith");
+        assert_eq!(record.age_range.as_deref(), Some("Age 40s"));
+        assert_eq!(record.current_address.as_deref(), Some("123 Main St, Philadelphia, PA"));
+        assert_eq!(record.past_addresses, vec!["9 Elm Rd, Camden, NJ"]);
+        assert_eq!(record.line_type.as_deref(), Some("Landline"));
+        assert_eq!(record.associated_numbers.len(), 2);
+        assert_eq!(record.source, "TestSite");
+    }
+
+    #[test]
+    fn test_parse_page_fallback_and_no_record() {
+        let html = "<html><body><h1>Jane Doe</h1><p>Lives in Camden, NJ</p></body></html>";
+        let records = TEST_SITE.parse(html, "u");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].full_name, "Jane Doe");
+
+        let html = "<html><body><h1>Jane Doe</h1><p>No records found. Lives in</p></body></html>";
+        assert!(TEST_SITE.parse(html, "u").is_empty());
+
+        let html = r#"<html><body><p>Lives in</p><span class="address">9 Elm Rd, Camden, NJ</span></body></html>"#;
+        assert!(TEST_SITE.parse(html, "u").is_empty());
+        let address_only = SiteSpec { address_only_pages: true, ..TEST_SITE };
+        let records = address_only.parse(html, "u");
+        assert_eq!(records.len(), 1);
+        assert!(records[0].full_name.is_empty());
+        assert_eq!(records[0].current_address.as_deref(), Some("9 Elm Rd, Camden, NJ"));
+    }
+}
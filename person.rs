@@ -0,0 +1,180 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Widest a table column may grow before its values are truncated
+const MAX_COLUMN_WIDTH: usize = 40;
+
+lazy_static! {
+    static ref PHONE_NUMBER_PATTERN: Regex =
+        Regex::new(r"\(?\b\d{3}\)?[-.\s]?\d{3}[-.\s]\d{4}\b").unwrap();
+}
+
+/// A person record returned by a people search site
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PersonRecord {
+    pub full_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub age_range: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_address: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub past_addresses: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub associated_numbers: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub carrier: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_type: Option<String>,
+    /// Site the record came from (e.g. "Whitepages")
+    pub source: String,
+    pub source_url: String,
+}
+
+impl PersonRecord {
+    pub fn new(full_name: &str, source: &str, source_url: &str) -> Self {
+        PersonRecord {
+            full_name: clean_text(full_name),
+            source: source.to_string(),
+            source_url: source_url.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Every address on the record, current first
+    pub fn addresses(&self) -> Vec<&str> {
+        self.current_address
+            .iter()
+            .chain(self.past_addresses.iter())
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Cell values in `TABLE_HEADERS` order
+    pub fn table_row(&self) -> Vec<String> {
+        vec![
+            self.full_name.clone(),
+            self.age_range.clone().unwrap_or_default(),
+            self.current_address.clone().unwrap_or_default(),
+            self.past_addresses.join("; "),
+            self.associated_numbers.join("; "),
+            self.carrier.clone().unwrap_or_default(),
+            self.line_type.clone().unwrap_or_default(),
+            self.source.clone(),
+            self.source_url.clone(),
+        ]
+    }
+}
+
+/// Column headers for tabular output of person records
+pub const TABLE_HEADERS: &[&str] = &[
+    "Name",
+    "Age",
+    "Current Address",
+    "Past Addresses",
+    "Associated Numbers",
+    "Carrier",
+    "Line Type",
+    "Source",
+    "Source URL",
+];
+
+/// Collapse runs of whitespace into single spaces
+pub fn clean_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Phone numbers mentioned in text, in order of first appearance
+pub fn extract_phone_numbers(text: &str) -> Vec<String> {
+    let mut numbers: Vec<String> = Vec::new();
+    for m in PHONE_NUMBER_PATTERN.find_iter(text) {
+        let number = m.as_str().to_string();
+        if !numbers.contains(&number) {
+            numbers.push(number);
+        }
+    }
+    numbers
+}
+
+/// Render records as a fixed-width text table. Empty columns are dropped
+/// and the source URL is left out to keep rows readable on a terminal.
+pub fn format_table(records: &[PersonRecord]) -> String {
+    let rows: Vec<Vec<String>> = records.iter().map(PersonRecord::table_row).collect();
+    let url_column = TABLE_HEADERS.len() - 1;
+
+    let columns: Vec<usize> = (0..url_column)
+        .filter(|&col| rows.iter().any(|row| !row[col].is_empty()))
+        .collect();
+
+    let widths: Vec<usize> = columns
+        .iter()
+        .map(|&col| {
+            rows.iter()
+                .map(|row| row[col].chars().count())
+                .chain(std::iter::once(TABLE_HEADERS[col].len()))
+                .max()
+                .unwrap_or(0)
+                .min(MAX_COLUMN_WIDTH)
+        })
+        .collect();
+
+    let format_row = |cells: Vec<&str>| -> String {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{:<width$}", truncate(cell, width), width = width))
+            .collect::<Vec<_>>()
+            .join(" | ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut table = String::new();
+    table.push_str(&format_row(columns.iter().map(|&col| TABLE_HEADERS[col]).collect()));
+    table.push('\n');
+    table.push_str(
+        &widths
+            .iter()
+            .map(|&width| "-".repeat(width))
+            .collect::<Vec<_>>()
+            .join("-+-"),
+    );
+    table.push('\n');
+    for row in &rows {
+        table.push_str(&format_row(columns.iter().map(|&col| row[col].as_str()).collect()));
+        table.push('\n');
+    }
+
+    table
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        text.to_string()
+    } else {
+        let cut: String = text.chars().take(width.saturating_sub(3)).collect();
+        format!("{}...", cut)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_phone_numbers() {
+        let text = "Also: (555) 555-1212, 555.555.3434 and (555) 555-1212 again";
+        assert_eq!(extract_phone_numbers(text), vec!["(555) 555-1212", "555.555.3434"]);
+    }
+
+    #[test]
+    fn test_format_table_drops_empty_columns() {
+        let mut record = PersonRecord::new("  John   Smith ", "Whitepages", "https://example.com");
+        record.current_address = Some("123 Main St, Philadelphia, PA".to_string());
+        let table = format_table(&[record]);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "Name       | Current Address               | Source");
+        assert!(lines[2].starts_with("John Smith | 123 Main St"));
+        assert!(!table.contains("Carrier"));
+    }
+}
@@ -50,6 +50,77 @@ impl PhoneFormatter {
             format!("{} {}-{}-{}", self.country, self.area, self.prefix, self.line),
         ]
     }
+
+    /// The 10-digit national number (area + prefix + line)
+    pub fn national_digits(&self) -> String {
+        format!("{}{}{}", self.area, self.prefix, self.line)
+    }
+
+    /// Check whether text contains this number in any separator style
+    /// (e.g. "555.555.1212", "555 555 1212", "+1 (555) 555-1212"). The
+    /// number must be made of whole digit groups, optionally after a 1, so
+    /// a ZIP or age written just before it doesn't hide it and longer digit
+    /// strings such as order IDs don't match.
+    pub fn matches_text(&self, text: &str) -> bool {
+        let national = self.national_digits();
+        let with_country = format!("1{}", national);
+        digit_chains(text).iter().any(|groups| {
+            (0..groups.len()).any(|start| {
+                let mut run = String::new();
+                for group in &groups[start..] {
+                    run.push_str(group);
+                    if run == national || run == with_country {
+                        return true;
+                    }
+                    if run.len() >= with_country.len() {
+                        break;
+                    }
+                }
+                false
+            })
+        })
+    }
+}
+
+/// Characters allowed between the digits of a written phone number
+const DIGIT_SEPARATORS: &[char] = &[' ', '-', '.', '(', ')', '/', '+', '\u{a0}', '\u{2013}'];
+
+/// Maximum consecutive separators tolerated between two digit groups
+const MAX_SEPARATOR_GAP: usize = 3;
+
+/// Split text into chains of digit groups: groups are unbroken digits, and a
+/// chain joins groups separated only by short stretches of phone-number
+/// punctuation ("ZIP 19102 (215) 555-1212" -> ["19102", "215", "555", "1212"]).
+fn digit_chains(text: &str) -> Vec<Vec<String>> {
+    let mut chains = Vec::new();
+    let mut chain: Vec<String> = Vec::new();
+    let mut group = String::new();
+    let mut gap = 0;
+
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            group.push(c);
+            gap = 0;
+            continue;
+        }
+        if !group.is_empty() {
+            chain.push(std::mem::take(&mut group));
+        }
+        if DIGIT_SEPARATORS.contains(&c) && !chain.is_empty() && gap < MAX_SEPARATOR_GAP {
+            gap += 1;
+        } else if !chain.is_empty() {
+            chains.push(std::mem::take(&mut chain));
+            gap = 0;
+        }
+    }
+    if !group.is_empty() {
+        chain.push(group);
+    }
+    if !chain.is_empty() {
+        chains.push(chain);
+    }
+
+    chains
 }
 
 #[cfg(test)]
@@ -81,6 +152,41 @@ mod tests {
         assert_eq!(formats[0], "555-555-1212");
     }
 
+    #[test]
+    fn test_matches_text_any_separator() {
+        let formatter = PhoneFormatter::new("5555551212").unwrap();
+        assert!(formatter.matches_text("Call 555.555.1212 today"));
+        assert!(formatter.matches_text("Call +1 (555) 555 - 1212"));
+        assert!(formatter.matches_text("555/555-1212"));
+        assert!(!formatter.matches_text("Call 555-555-1213 today"));
+        assert!(!formatter.matches_text("555-555 and then 1212"));
+    }
+
+    #[test]
+    fn test_matches_text_rejects_neighbours_and_longer_runs() {
+        let formatter = PhoneFormatter::new("2125551212").unwrap();
+        assert!(formatter.matches_text("call 555 555 1212 or 212 555 1212"));
+        assert!(formatter.matches_text("1 212 555 1212"));
+        assert!(!formatter.matches_text("call 555 555 1212 212 555 0000"));
+        assert!(!formatter.matches_text("Order #9212555121200 shipped"));
+        assert!(!formatter.matches_text("ts 1700212555121234"));
+        assert!(!formatter.matches_text("x 42125551212"));
+        assert_eq!(digit_chains("555-555 and 1212"), vec![vec!["555", "555"], vec!["1212"]]);
+    }
+
+    #[test]
+    fn test_matches_text_after_leading_digits() {
+        let formatter = PhoneFormatter::new("2155551212").unwrap();
+        assert!(formatter.matches_text("Age 45 215-555-1212"));
+        assert!(formatter.matches_text("Suite 100 (215) 555-1212"));
+        assert!(formatter.matches_text("Philadelphia, PA 19102 (215) 555-1212"));
+        assert!(formatter.matches_text("Page 2 - 215-555-1212"));
+        assert!(formatter.matches_text("123 Main St 1-215-555-1212"));
+        assert!(formatter.matches_text("215-555-1212 Age 45"));
+        assert!(!formatter.matches_text("Philadelphia, PA 19102 (215) 555-1213"));
+        assert!(!formatter.matches_text("ref 19102215555121299"));
+    }
+
     #[test]
     fn test_invalid_length() {
         let result = PhoneFormatter::new("123");
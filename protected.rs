@@ -0,0 +1,151 @@
+use anyhow::{anyhow, Context, Result};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::config::config_dir;
+
+/// Bytes of random salt per entry
+const SALT_LEN: usize = 16;
+
+/// One protected number, stored as `<salt hex>:<sha256(salt || digits) hex>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtectedEntry {
+    pub salt: Vec<u8>,
+    pub hash: String,
+}
+
+impl ProtectedEntry {
+    /// Hash a normalized (10-digit national) number with a fresh salt
+    pub fn new(digits: &str) -> Self {
+        let salt = rand::random::<[u8; SALT_LEN]>().to_vec();
+        let hash = salted_hash(&salt, digits);
+        ProtectedEntry { salt, hash }
+    }
+
+    pub fn parse(line: &str) -> Result<Self> {
+        let (salt, hash) = line
+            .split_once(':')
+            .ok_or_else(|| anyhow!("expected <salt>:<hash>"))?;
+        Ok(ProtectedEntry {
+            salt: hex::decode(salt.trim())?,
+            hash: hash.trim().to_lowercase(),
+        })
+    }
+
+    pub fn matches(&self, digits: &str) -> bool {
+        salted_hash(&self.salt, digits) == self.hash
+    }
+
+    pub fn to_line(&self) -> String {
+        format!("{}:{}", hex::encode(&self.salt), self.hash)
+    }
+}
+
+fn salted_hash(salt: &[u8], digits: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt);
+    hasher.update(digits.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// The do-not-search list. Numbers are never stored in the clear.
+#[derive(Debug, Default)]
+pub struct ProtectedList {
+    entries: Vec<ProtectedEntry>,
+}
+
+impl ProtectedList {
+    /// Load a list file; a missing file is an empty list. Blank lines and
+    /// lines starting with `#` are ignored.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(ProtectedList::default());
+        }
+
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read protected list {}", path.display()))?;
+        let mut entries = Vec::new();
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let entry = ProtectedEntry::parse(line)
+                .with_context(|| format!("{}:{}: invalid entry", path.display(), i + 1))?;
+            entries.push(entry);
+        }
+        Ok(ProtectedList { entries })
+    }
+
+    /// The entry matching a normalized number, if it is protected
+    pub fn find(&self, digits: &str) -> Option<&ProtectedEntry> {
+        self.entries.iter().find(|entry| entry.matches(digits))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Protected list location: the configured path or `~/.config/telespotter/protected.txt`
+pub fn list_path(configured: Option<&str>) -> Result<PathBuf> {
+    configured
+        .map(PathBuf::from)
+        .or_else(|| config_dir().map(|dir| dir.join("protected.txt")))
+        .ok_or_else(|| anyhow!("Cannot locate protected list: set \"protected_numbers\" in config or HOME"))
+}
+
+/// Append a salted hash of a normalized number to the list file
+pub fn add(path: &Path, digits: &str) -> Result<ProtectedEntry> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+
+    let entry = ProtectedEntry::new(digits);
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open protected list {}", path.display()))?;
+    writeln!(file, "{}", entry.to_line())?;
+    Ok(entry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_salted_entries_match_only_their_number() {
+        let a = ProtectedEntry::new("5555551212");
+        let b = ProtectedEntry::new("5555551212");
+        assert_ne!(a.hash, b.hash);
+        assert!(a.matches("5555551212") && b.matches("5555551212"));
+        assert!(!a.matches("5555551213"));
+
+        let line = a.to_line();
+        assert!(!line.contains("5555551212"));
+        assert_eq!(ProtectedEntry::parse(&line).unwrap(), a);
+    }
+
+    #[test]
+    fn test_load_list() {
+        let path = std::env::temp_dir().join(format!("telespotter_protected_{}.txt", std::process::id()));
+        fs::write(&path, "# staff\n\n").unwrap();
+        add(&path, "2155550100").unwrap();
+
+        let list = ProtectedList::load(&path).unwrap();
+        assert_eq!(list.len(), 1);
+        assert!(list.find("2155550100").is_some());
+        assert!(list.find("5555551212").is_none());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,89 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+/// Permissible purposes accepted when the config doesn't list its own
+pub const DEFAULT_PURPOSES: &[&str] = &[
+    "fraud-prevention",
+    "law-enforcement",
+    "legal-proceedings",
+    "insurance-claim",
+    "debt-collection",
+    "subject-consent",
+];
+
+/// Who ran a people search and why, stamped into saved output
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Authorization {
+    pub purpose: String,
+    pub operator: String,
+    pub timestamp: String,
+}
+
+/// Purposes accepted for people search: the configured list, or the defaults
+pub fn allowed_purposes(configured: &[String]) -> Vec<String> {
+    if configured.is_empty() {
+        DEFAULT_PURPOSES.iter().map(|p| p.to_string()).collect()
+    } else {
+        configured.to_vec()
+    }
+}
+
+/// Check a people search request has a listed purpose and an operator.
+///
+/// Purposes match case-insensitively; the listed spelling is recorded.
+pub fn authorize(
+    purpose: Option<&str>,
+    operator: Option<&str>,
+    configured: &[String],
+) -> Result<Authorization> {
+    let allowed = allowed_purposes(configured);
+
+    let purpose = purpose.map(str::trim).filter(|p| !p.is_empty()).ok_or_else(|| {
+        anyhow!(
+            "People search requires --purpose (one of: {})",
+            allowed.join(", ")
+        )
+    })?;
+    let purpose = allowed
+        .iter()
+        .find(|p| p.eq_ignore_ascii_case(purpose))
+        .ok_or_else(|| {
+            anyhow!(
+                "'{}' is not a permissible purpose (one of: {})",
+                purpose,
+                allowed.join(", ")
+            )
+        })?;
+
+    let operator = operator
+        .map(str::trim)
+        .filter(|o| !o.is_empty())
+        .ok_or_else(|| anyhow!("People search requires --operator (or \"operator\" in config)"))?;
+
+    Ok(Authorization {
+        purpose: purpose.clone(),
+        operator: operator.to_string(),
+        timestamp: Utc::now().to_rfc3339(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_authorize() {
+        let auth = authorize(Some("Fraud-Prevention"), Some("jdoe"), &[]).unwrap();
+        assert_eq!(auth.purpose, "fraud-prevention");
+        assert_eq!(auth.operator, "jdoe");
+
+        assert!(authorize(None, Some("jdoe"), &[]).is_err());
+        assert!(authorize(Some("curiosity"), Some("jdoe"), &[]).is_err());
+        assert!(authorize(Some("fraud-prevention"), Some("  "), &[]).is_err());
+
+        let configured = vec!["tenant-screening".to_string()];
+        assert!(authorize(Some("fraud-prevention"), Some("jdoe"), &configured).is_err());
+        assert!(authorize(Some("tenant-screening"), Some("jdoe"), &configured).is_ok());
+    }
+}
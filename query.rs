@@ -0,0 +1,227 @@
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Placeholder replaced by each generated phone format
+pub const FORMAT_PLACEHOLDER: &str = "{fmt}";
+
+/// Template used when none is selected
+pub const DEFAULT_TEMPLATE: &str = "exact";
+
+/// Built-in dork presets (name, template)
+const BUILTIN_PRESETS: &[(&str, &str)] = &[
+    ("exact", "\"{fmt}\""),
+    ("facebook", "\"{fmt}\" site:facebook.com"),
+    ("linkedin", "\"{fmt}\" site:linkedin.com"),
+    ("social", "\"{fmt}\" (site:facebook.com OR site:instagram.com OR site:twitter.com OR site:x.com OR site:tiktok.com)"),
+    ("complaints", "\"{fmt}\" (scam OR fraud OR complaint OR spam)"),
+    ("business", "\"{fmt}\" (LLC OR Inc OR company OR business)"),
+    ("classifieds", "\"{fmt}\" (site:craigslist.org OR site:offerup.com OR site:ebay.com)"),
+    ("no-directories", "\"{fmt}\" -site:800notes.com -site:whocalledme.com -site:callercenter.com -site:shouldianswer.com"),
+];
+
+/// How a phone format is matched by the search engines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchMode {
+    /// Quoted phrase search for the exact format
+    #[default]
+    Exact,
+    /// Unquoted search, post-filtered to results containing the number
+    Broad,
+    /// Run both exact and broad queries
+    Both,
+}
+
+impl MatchMode {
+    /// The concrete modes to run (expands `Both`)
+    pub fn expand(self) -> Vec<MatchMode> {
+        match self {
+            MatchMode::Both => vec![MatchMode::Exact, MatchMode::Broad],
+            mode => vec![mode],
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MatchMode::Exact => "exact",
+            MatchMode::Broad => "broad",
+            MatchMode::Both => "both",
+        }
+    }
+}
+
+/// A named search query template
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueryTemplate {
+    pub name: String,
+    pub template: String,
+}
+
+impl QueryTemplate {
+    pub fn new(name: &str, template: &str) -> Self {
+        QueryTemplate {
+            name: name.to_string(),
+            template: template.to_string(),
+        }
+    }
+
+    /// Substitute a phone format into the template.
+    ///
+    /// Exact mode always quotes the format; broad mode strips any quotes the
+    /// template put around the placeholder.
+    pub fn render(&self, format: &str, mode: MatchMode) -> String {
+        let quoted_placeholder = format!("\"{}\"", FORMAT_PLACEHOLDER);
+        let template = self.template.replace(&quoted_placeholder, FORMAT_PLACEHOLDER);
+        let value = match mode {
+            MatchMode::Broad => format.to_string(),
+            _ => format!("\"{}\"", format),
+        };
+        template.replace(FORMAT_PLACEHOLDER, &value)
+    }
+}
+
+/// A single query to send to the search engines
+#[derive(Debug, Clone)]
+pub struct SearchQuery {
+    pub template: String,
+    pub match_mode: MatchMode,
+    pub format: String,
+    pub query: String,
+}
+
+/// All built-in presets, overridden or extended by user-defined templates
+pub fn available_templates(custom: &HashMap<String, String>) -> Vec<QueryTemplate> {
+    let mut templates: Vec<QueryTemplate> = BUILTIN_PRESETS
+        .iter()
+        .map(|(name, template)| {
+            let template = custom.get(*name).map(String::as_str).unwrap_or(template);
+            QueryTemplate::new(name, template)
+        })
+        .collect();
+
+    let mut extra: Vec<(&String, &String)> = custom
+        .iter()
+        .filter(|(name, _)| !BUILTIN_PRESETS.iter().any(|(builtin, _)| builtin == name))
+        .collect();
+    extra.sort();
+    templates.extend(extra.into_iter().map(|(name, template)| QueryTemplate::new(name, template)));
+
+    templates
+}
+
+/// Resolve the templates selected on the command line.
+///
+/// Each selection is either a preset name or a literal template containing
+/// `{fmt}`, in which case the literal doubles as its name.
+pub fn resolve_templates(
+    selected: &[String],
+    custom: &HashMap<String, String>,
+) -> Result<Vec<QueryTemplate>> {
+    let available = available_templates(custom);
+    let selected: Vec<&str> = if selected.is_empty() {
+        vec![DEFAULT_TEMPLATE]
+    } else {
+        selected.iter().map(String::as_str).collect()
+    };
+
+    let mut templates: Vec<QueryTemplate> = Vec::new();
+    for selection in selected {
+        let template = if let Some(preset) = available.iter().find(|t| t.name == selection) {
+            preset.clone()
+        } else if selection.contains(FORMAT_PLACEHOLDER) {
+            QueryTemplate::new(selection, selection)
+        } else {
+            return Err(anyhow!(
+                "Unknown query template '{}' (use --list-templates to see presets, or include {} in a custom template)",
+                selection,
+                FORMAT_PLACEHOLDER
+            ));
+        };
+
+        if !templates.contains(&template) {
+            templates.push(template);
+        }
+    }
+
+    Ok(templates)
+}
+
+/// Expand every template against every phone format in each match mode
+pub fn build_queries(
+    templates: &[QueryTemplate],
+    formats: &[String],
+    match_mode: MatchMode,
+) -> Vec<SearchQuery> {
+    let mut queries = Vec::new();
+    for mode in match_mode.expand() {
+        for template in templates {
+            for format in formats {
+                queries.push(SearchQuery {
+                    template: template.name.clone(),
+                    match_mode: mode,
+                    format: format.clone(),
+                    query: template.render(format, mode),
+                });
+            }
+        }
+    }
+    queries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let template = QueryTemplate::new("facebook", "\"{fmt}\" site:facebook.com");
+        assert_eq!(
+            template.render("555-555-1212", MatchMode::Exact),
+            "\"555-555-1212\" site:facebook.com"
+        );
+        assert_eq!(
+            template.render("555-555-1212", MatchMode::Broad),
+            "555-555-1212 site:facebook.com"
+        );
+
+        let unquoted = QueryTemplate::new("reddit", "{fmt} site:reddit.com");
+        assert_eq!(unquoted.render("5555551212", MatchMode::Exact), "\"5555551212\" site:reddit.com");
+    }
+
+    #[test]
+    fn test_resolve_default_and_custom() {
+        let mut custom = HashMap::new();
+        custom.insert("reviews".to_string(), "\"{fmt}\" reviews".to_string());
+
+        let templates = resolve_templates(&[], &custom).unwrap();
+        assert_eq!(templates[0].name, DEFAULT_TEMPLATE);
+
+        let templates = resolve_templates(&["reviews".to_string()], &custom).unwrap();
+        assert_eq!(templates[0].template, "\"{fmt}\" reviews");
+
+        let templates = resolve_templates(&["{fmt} site:reddit.com".to_string()], &custom).unwrap();
+        assert_eq!(templates[0].name, "{fmt} site:reddit.com");
+
+        assert!(resolve_templates(&["nonexistent".to_string()], &custom).is_err());
+    }
+
+    #[test]
+    fn test_build_queries() {
+        let templates = vec![
+            QueryTemplate::new("exact", "\"{fmt}\""),
+            QueryTemplate::new("facebook", "\"{fmt}\" site:facebook.com"),
+        ];
+        let formats = vec!["555-555-1212".to_string(), "5555551212".to_string()];
+        let queries = build_queries(&templates, &formats, MatchMode::Exact);
+        assert_eq!(queries.len(), 4);
+        assert_eq!(queries[3].template, "facebook");
+        assert_eq!(queries[3].query, "\"5555551212\" site:facebook.com");
+
+        let queries = build_queries(&templates, &formats, MatchMode::Both);
+        assert_eq!(queries.len(), 8);
+        assert_eq!(queries[4].match_mode, MatchMode::Broad);
+        assert_eq!(queries[4].query, "555-555-1212");
+    }
+}
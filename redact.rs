@@ -0,0 +1,295 @@
+use clap::ValueEnum;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::dedup::MergedResult;
+use crate::person::PersonRecord;
+use crate::search::SearchResult;
+
+lazy_static! {
+    static ref EMAIL_PATTERN: Regex =
+        Regex::new(r"(?i)\b[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}\b").unwrap();
+    /// Last "City, ST" in an address
+    static ref CITY_STATE_PATTERN: Regex =
+        Regex::new(r"([A-Za-z][A-Za-z .'-]*?),\s*([A-Z]{2})\b").unwrap();
+    static ref STREET_PATTERN: Regex = Regex::new(
+        r"\b\d{1,6}\s+(?:[A-Za-z0-9.]+\s+){1,4}(?:St|Street|Ave|Avenue|Rd|Road|Blvd|Boulevard|Dr|Drive|Ln|Lane|Ct|Court|Way|Pl|Place|Ter|Terrace|Pkwy|Parkway|Cir|Circle|Hwy|Highway)\b\.?"
+    )
+    .unwrap();
+    /// A bare ZIP or Canadian postal code
+    static ref ZIP_ONLY_PATTERN: Regex =
+        Regex::new(r"^(?:\d{5}(?:-\d{4})?|[A-Z]\d[A-Z] \d[A-Z]\d)$").unwrap();
+}
+
+/// How much personal detail to hide in console output and saved files
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RedactLevel {
+    #[default]
+    None,
+    /// Mask emails and the target number's middle digits
+    Partial,
+    /// Partial, plus street addresses cut to city/state and initial-only names
+    Full,
+}
+
+impl RedactLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RedactLevel::None => "none",
+            RedactLevel::Partial => "partial",
+            RedactLevel::Full => "full",
+        }
+    }
+}
+
+/// Applies a redaction level to values and free text
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    level: RedactLevel,
+    target: Option<(String, Regex)>,
+    names: Vec<String>,
+}
+
+impl Redactor {
+    /// `digits` is the target's 10-digit national number
+    pub fn new(level: RedactLevel, digits: &str) -> Self {
+        let target = (digits.len() == 10).then(|| {
+            let sep = r"[\s.\-/()]*";
+            let pattern = format!(
+                r"(?:\+?1{sep})?\(?{}\)?{sep}{}{sep}{}",
+                &digits[0..3],
+                &digits[3..6],
+                &digits[6..10],
+                sep = sep
+            );
+            let masked = format!("{}-***-{}", &digits[0..3], &digits[6..10]);
+            (masked, Regex::new(&pattern).unwrap())
+        });
+        Redactor {
+            level,
+            target,
+            names: Vec::new(),
+        }
+    }
+
+    /// Names to replace with initials in free text (full level)
+    pub fn with_names<I: IntoIterator<Item = String>>(mut self, names: I) -> Self {
+        self.names.extend(names.into_iter().filter(|n| !n.trim().is_empty()));
+        // Longest first so "John A Smith" goes before "John A"
+        self.names.sort_by_key(|n| std::cmp::Reverse(n.len()));
+        self.names.dedup();
+        self
+    }
+
+    pub fn level(&self) -> RedactLevel {
+        self.level
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.level != RedactLevel::None
+    }
+
+    fn full(&self) -> bool {
+        self.level == RedactLevel::Full
+    }
+
+    /// The target number as it may be shown
+    pub fn number(&self, number: &str) -> String {
+        match &self.target {
+            Some((masked, _)) if self.is_active() => masked.clone(),
+            _ => number.to_string(),
+        }
+    }
+
+    /// `john.smith@gmail.com` -> `j***@gmail.com`
+    pub fn email(&self, email: &str) -> String {
+        if !self.is_active() {
+            return email.to_string();
+        }
+        match email.split_once('@') {
+            Some((local, domain)) => {
+                let first = local.chars().next().map(String::from).unwrap_or_default();
+                format!("{}***@{}", first, domain)
+            }
+            None => "***".to_string(),
+        }
+    }
+
+    /// `John Q Smith` -> `J. Q. S.` at the full level
+    pub fn name(&self, name: &str) -> String {
+        if !self.full() {
+            return name.to_string();
+        }
+        name.split_whitespace()
+            .filter_map(|word| word.chars().find(|c| c.is_alphabetic()))
+            .map(|c| format!("{}.", c.to_uppercase()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Street address cut to "City, ST" at the full level
+    pub fn address(&self, address: &str) -> String {
+        if !self.full() {
+            return address.to_string();
+        }
+        match CITY_STATE_PATTERN.captures_iter(address).last() {
+            Some(cap) => {
+                let city = cap[1].rsplit(',').next().unwrap_or("").trim();
+                // Drop a leading street part that shares the comma-less segment
+                let city = STREET_PATTERN.replace(city, "");
+                format!("{}, {}", city.trim(), &cap[2])
+            }
+            None => "[address redacted]".to_string(),
+        }
+    }
+
+    /// A location from analysis; bare ZIP and postal codes are dropped at the full level
+    pub fn location(&self, location: &str) -> Option<String> {
+        if self.full() && ZIP_ONLY_PATTERN.is_match(location) {
+            None
+        } else {
+            Some(location.to_string())
+        }
+    }
+
+    /// Free text such as titles, snippets, URLs and queries
+    pub fn text(&self, text: &str) -> String {
+        if !self.is_active() {
+            return text.to_string();
+        }
+
+        let mut text = EMAIL_PATTERN
+            .replace_all(text, |caps: &regex::Captures| self.email(&caps[0]))
+            .into_owned();
+        if let Some((masked, pattern)) = &self.target {
+            text = pattern.replace_all(&text, masked.as_str()).into_owned();
+        }
+
+        if self.full() {
+            text = STREET_PATTERN.replace_all(&text, "[street address]").into_owned();
+            for name in &self.names {
+                text = text.replace(name.as_str(), &self.name(name));
+            }
+        }
+        text
+    }
+
+    pub fn result(&self, result: &SearchResult) -> SearchResult {
+        let mut redacted = result.clone();
+        redacted.title = self.text(&result.title);
+        redacted.snippet = self.text(&result.snippet);
+        redacted.url = self.text(&result.url);
+        redacted.format = self.text(&result.format);
+        redacted.spam_reason = result.spam_reason.as_deref().map(|reason| self.text(reason));
+        redacted
+    }
+
+    /// A merged page, including its key and the queries and formats of every hit
+    pub fn page(&self, page: &MergedResult) -> MergedResult {
+        let mut redacted = page.clone();
+        redacted.key = self.text(&page.key);
+        redacted.result = self.result(&page.result);
+        for hit in &mut redacted.hits {
+            hit.query = self.text(&hit.query);
+            hit.format = self.text(&hit.format);
+        }
+        redacted
+    }
+
+    pub fn person(&self, record: &PersonRecord) -> PersonRecord {
+        let mut redacted = record.clone();
+        redacted.full_name = self.name(&record.full_name);
+        redacted.current_address = record.current_address.as_deref().map(|a| self.address(a));
+        redacted.past_addresses = record.past_addresses.iter().map(|a| self.address(a)).collect();
+        redacted.associated_numbers = record
+            .associated_numbers
+            .iter()
+            .map(|n| if self.full() { mask_number(n) } else { self.text(n) })
+            .collect();
+        redacted.source_url = self.text(&record.source_url);
+        redacted
+    }
+}
+
+/// Mask the middle digits of any phone number
+fn mask_number(number: &str) -> String {
+    let digits: String = number.chars().filter(|c| c.is_ascii_digit()).collect();
+    let national = digits.strip_prefix('1').filter(|d| d.len() == 10).unwrap_or(&digits);
+    if national.len() == 10 {
+        format!("{}-***-{}", &national[0..3], &national[6..10])
+    } else {
+        "***".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_masks_emails_and_number() {
+        let redactor = Redactor::new(RedactLevel::Partial, "5555551212");
+        assert_eq!(redactor.email("john.smith@gmail.com"), "j***@gmail.com");
+        assert_eq!(redactor.number("(555) 555-1212"), "555-***-1212");
+        assert_eq!(
+            redactor.text("Call +1 (555) 555.1212 or mail jo@x.org - John Smith"),
+            "Call 555-***-1212 or mail j***@x.org - John Smith"
+        );
+        assert_eq!(redactor.name("John Smith"), "John Smith");
+    }
+
+    #[test]
+    fn test_full_redacts_names_and_addresses() {
+        let redactor = Redactor::new(RedactLevel::Full, "5555551212")
+            .with_names(vec!["John Smith".to_string()]);
+        assert_eq!(redactor.name("John Q Smith"), "J. Q. S.");
+        assert_eq!(redactor.address("123 Main St, Philadelphia, PA 19102"), "Philadelphia, PA");
+        assert_eq!(redactor.address("somewhere"), "[address redacted]");
+        assert_eq!(
+            redactor.text("John Smith lives at 42 Oak Ave in Dover"),
+            "J. S. lives at [street address] in Dover"
+        );
+        assert_eq!(redactor.location("19102"), None);
+        assert_eq!(redactor.location("M5V 2T6"), None);
+
+        let unredacted = Redactor::new(RedactLevel::None, "5555551212");
+        assert_eq!(unredacted.text("555-555-1212 jo@x.org"), "555-555-1212 jo@x.org");
+    }
+
+    #[test]
+    fn test_saved_run_never_contains_target_digits() {
+        let mut result = SearchResult::new(
+            "(555) 555-1212 - who called?".to_string(),
+            "Owner of 555.555.1212".to_string(),
+            "Google".to_string(),
+        );
+        result.format = "555-555-1212".to_string();
+        result.spam_reason = Some("page lists 5555551212 among 40 numbers".to_string());
+        let no_url = result.clone();
+        let result = result.with_url("https://example.com/1-555-555-1212");
+        let all_results = std::collections::HashMap::from([("\"555-555-1212\"".to_string(), vec![result, no_url])]);
+        let mut record = PersonRecord::new("John Smith", "Whitepages", "https://wp.example/5555551212");
+        record.associated_numbers = vec!["(555) 555-1212".to_string()];
+
+        for level in [RedactLevel::Partial, RedactLevel::Full] {
+            let redactor = Redactor::new(level, "5555551212");
+            let saved: std::collections::HashMap<String, Vec<SearchResult>> = all_results
+                .iter()
+                .map(|(query, results)| (redactor.text(query), results.iter().map(|r| redactor.result(r)).collect()))
+                .collect();
+            let pages: Vec<MergedResult> =
+                crate::dedup::merge_results(&all_results).iter().map(|p| redactor.page(p)).collect();
+            let json = serde_json::json!({
+                "results": saved,
+                "pages": pages,
+                "people_records": [redactor.person(&record)],
+            })
+            .to_string();
+            let digits: String = json.chars().filter(|c| c.is_ascii_digit() || *c == '*').collect();
+            assert!(!digits.contains("5555551212"), "{}", json);
+            assert!(!json.contains("555-1212") && !json.contains("555.1212"), "{}", json);
+        }
+    }
+}
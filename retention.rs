@@ -0,0 +1,305 @@
+use crate::audit::AuditEntry;
+use crate::case::{sanitize, NO_CASE_DIR};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Default number of days saved data is kept
+pub const DEFAULT_RETENTION_DAYS: u32 = 90;
+
+/// Format of the timestamp folder in `<case>/<number>/<timestamp>`
+const RUN_DIR_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Overwrite buffer size for secure deletion
+const WIPE_CHUNK: usize = 64 * 1024;
+
+/// How long saved data is kept
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionPolicy {
+    pub days: u32,
+    /// Case ids whose data is kept regardless of age
+    pub open_cases: Vec<String>,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        RetentionPolicy {
+            days: DEFAULT_RETENTION_DAYS,
+            open_cases: Vec::new(),
+        }
+    }
+}
+
+impl RetentionPolicy {
+    pub fn cutoff(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now - Duration::days(i64::from(self.days))
+    }
+
+    /// Whether a case folder or case id belongs to an open case
+    pub fn is_open(&self, case: &str) -> bool {
+        let label = sanitize(case);
+        label != NO_CASE_DIR && self.open_cases.iter().any(|open| sanitize(open) == label)
+    }
+}
+
+/// What a purge will delete
+#[derive(Debug, Default)]
+pub struct PurgePlan {
+    /// Expired run folders under the case root
+    pub run_dirs: Vec<PathBuf>,
+    /// Expired files recorded in the audit log that live outside the case root
+    pub files: Vec<PathBuf>,
+}
+
+impl PurgePlan {
+    pub fn is_empty(&self) -> bool {
+        self.run_dirs.is_empty() && self.files.is_empty()
+    }
+}
+
+/// Find expired data: run folders under `case_root` older than the cutoff,
+/// and files referenced by expired audit entries. Open cases are skipped, as
+/// are files a newer entry also references or that were written since the
+/// cutoff.
+pub fn plan(
+    case_root: &Path,
+    policy: &RetentionPolicy,
+    audit_entries: &[AuditEntry],
+    now: DateTime<Utc>,
+) -> Result<PurgePlan> {
+    let cutoff = policy.cutoff(now);
+    let mut plan = PurgePlan::default();
+
+    if case_root.is_dir() {
+        for case_dir in subdirs(case_root)? {
+            let case_name = file_name(&case_dir);
+            if policy.is_open(&case_name) {
+                continue;
+            }
+            for number_dir in subdirs(&case_dir)? {
+                for run_dir in subdirs(&number_dir)? {
+                    // Folders that don't carry a run timestamp are left alone
+                    let Ok(started) = NaiveDateTime::parse_from_str(&file_name(&run_dir), RUN_DIR_FORMAT) else {
+                        continue;
+                    };
+                    if started.and_utc() < cutoff {
+                        plan.run_dirs.push(run_dir);
+                    }
+                }
+            }
+        }
+    }
+
+    // A path reused by a later run (e.g. the same `-o`) belongs to that run
+    let mut kept: Vec<PathBuf> = Vec::new();
+    let mut expired_files: Vec<PathBuf> = Vec::new();
+    for entry in audit_entries {
+        let expired = DateTime::parse_from_rfc3339(&entry.timestamp)
+            .map(|t| t.with_timezone(&Utc) < cutoff)
+            .unwrap_or(false);
+        let open = entry.case_id.as_deref().map(|c| policy.is_open(c)).unwrap_or(false);
+        let paths = entry.files.iter().map(PathBuf::from);
+        if expired && !open {
+            expired_files.extend(paths);
+        } else {
+            kept.extend(paths);
+        }
+    }
+    // The audit log records absolute paths while the case root may be relative
+    let planned_dirs: Vec<PathBuf> = plan.run_dirs.iter().map(|dir| canonical(dir)).collect();
+    let kept: Vec<PathBuf> = kept.iter().map(|path| canonical(path)).collect();
+    for path in expired_files {
+        let in_planned_dir = planned_dirs.iter().any(|dir| canonical(&path).starts_with(dir));
+        if path.is_file()
+            && !in_planned_dir
+            && !kept.contains(&canonical(&path))
+            && !plan.files.contains(&path)
+            && modified_before(&path, cutoff)
+        {
+            plan.files.push(path);
+        }
+    }
+
+    Ok(plan)
+}
+
+/// Delete everything in the plan, removing case/number folders left empty.
+/// Returns every file deleted.
+pub fn execute(plan: &PurgePlan) -> Result<Vec<PathBuf>> {
+    let mut removed = Vec::new();
+
+    for dir in &plan.run_dirs {
+        for file in files_in(dir)? {
+            secure_delete(&file)?;
+            removed.push(file);
+        }
+        fs::remove_dir_all(dir).with_context(|| format!("Failed to remove {}", dir.display()))?;
+
+        // Drop the number and case folders once they're empty
+        for parent in dir.ancestors().skip(1).take(2) {
+            if fs::remove_dir(parent).is_err() {
+                break;
+            }
+        }
+    }
+
+    for file in &plan.files {
+        secure_delete(file)?;
+        removed.push(file.clone());
+    }
+
+    Ok(removed)
+}
+
+/// Overwrite a file with zeros, flush it to disk, then unlink it.
+///
+/// On copy-on-write filesystems and SSDs the old blocks may survive; full-disk
+/// encryption or `--encrypt` is the real protection there.
+pub fn secure_delete(path: &Path) -> Result<()> {
+    let len = fs::metadata(path)
+        .with_context(|| format!("Failed to stat {}", path.display()))?
+        .len();
+    let mut file = OpenOptions::new()
+        .write(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {} for wiping", path.display()))?;
+
+    let zeros = vec![0u8; WIPE_CHUNK];
+    let mut remaining = len as usize;
+    while remaining > 0 {
+        let n = remaining.min(WIPE_CHUNK);
+        file.write_all(&zeros[..n])?;
+        remaining -= n;
+    }
+    file.sync_all()?;
+    drop(file);
+
+    fs::remove_file(path).with_context(|| format!("Failed to delete {}", path.display()))
+}
+
+/// Absolute form of a path with links resolved, or the path itself if it is gone
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Whether a file was last written before the cutoff
+fn modified_before(path: &Path, cutoff: DateTime<Utc>) -> bool {
+    fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .map(|modified| DateTime::<Utc>::from(modified) < cutoff)
+        .unwrap_or(false)
+}
+
+fn subdirs(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            dirs.push(path);
+        }
+    }
+    dirs.sort();
+    Ok(dirs)
+}
+
+fn files_in(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(files_in(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_plan_and_purge() {
+        let root = std::env::temp_dir().join(format!("telespotter_retention_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let old_run = root.join("C-1/5555551212/20240101T000000Z");
+        let new_run = root.join("C-1/5555551212/20240601T000000Z");
+        let open_run = root.join("C-2/5555551212/20240101T000000Z");
+        for dir in [&old_run, &new_run, &open_run] {
+            fs::create_dir_all(dir).unwrap();
+            fs::write(dir.join("results.json"), "{}").unwrap();
+        }
+        let written = |path: &Path, when: DateTime<Utc>| {
+            fs::write(path, "a,b").unwrap();
+            fs::File::options().write(true).open(path).unwrap().set_modified(when.into()).unwrap();
+        };
+        let loose = root.join("exported.csv");
+        let reused = root.join("results.json");
+        let rewritten = root.join("rewritten.json");
+        written(&loose, Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+        // Old timestamps alone would expire it; the newer entry keeps it
+        written(&reused, Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+        written(&rewritten, Utc.with_ymd_and_hms(2024, 5, 30, 0, 0, 0).unwrap());
+
+        let mut entry = AuditEntry::new("lookup", "5555551212");
+        entry.timestamp = "2024-01-01T00:00:00+00:00".to_string();
+        entry.files = [&loose, &reused, &rewritten].iter().map(|p| p.display().to_string()).collect();
+        // A later run saved over the same `-o` path
+        let mut newer = AuditEntry::new("lookup", "5555551212");
+        newer.timestamp = "2024-05-30T00:00:00+00:00".to_string();
+        newer.files = vec![reused.display().to_string()];
+
+        let policy = RetentionPolicy {
+            days: 90,
+            open_cases: vec!["C-2".to_string()],
+        };
+        let now = Utc.with_ymd_and_hms(2024, 6, 2, 0, 0, 0).unwrap();
+        let plan = plan(&root, &policy, &[entry, newer], now).unwrap();
+        assert_eq!(plan.run_dirs, vec![old_run.clone()]);
+        assert_eq!(plan.files, vec![loose.clone()]);
+
+        let removed = execute(&plan).unwrap();
+        assert_eq!(removed.len(), 2);
+        assert!(!old_run.exists() && !loose.exists());
+        assert!(new_run.exists() && open_run.exists());
+        assert!(reused.exists() && rewritten.exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_relative_case_root_matches_absolute_audit_paths() {
+        let root = PathBuf::from(format!("target/telespotter_retention_rel_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let run = root.join("no-case/5555551212/20240101T000000Z");
+        fs::create_dir_all(&run).unwrap();
+        let results = run.join("results.json");
+        fs::write(&results, "{}").unwrap();
+
+        // Saved runs are logged with canonical paths
+        let mut entry = AuditEntry::new("lookup", "5555551212");
+        entry.timestamp = "2024-01-01T00:00:00+00:00".to_string();
+        entry.files = vec![fs::canonicalize(&results).unwrap().display().to_string()];
+
+        let now = Utc.with_ymd_and_hms(2024, 6, 2, 0, 0, 0).unwrap();
+        let plan = plan(&root, &RetentionPolicy::default(), &[entry], now).unwrap();
+        assert_eq!(plan.run_dirs, vec![run.clone()]);
+        assert!(plan.files.is_empty(), "{:?}", plan.files);
+
+        assert_eq!(execute(&plan).unwrap(), vec![results]);
+        assert!(!run.exists());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
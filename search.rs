@@ -1,3 +1,5 @@
+use crate::phone::PhoneFormatter;
+use crate::query::MatchMode;
 use rand::seq::SliceRandom;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
@@ -60,6 +62,30 @@ pub struct SearchResult {
     pub title: String,
     pub snippet: String,
     pub source: String,
+    /// Link to the result page (empty when the engine did not provide one)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub url: String,
+    /// Phone format searched for
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub format: String,
+    /// 1-based position in the engine's result list
+    #[serde(default)]
+    pub rank: usize,
+    /// Name of the query template that produced this result
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub template: String,
+    /// Match mode of the query that produced this result
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_mode: Option<MatchMode>,
+    /// The result text or URL contains the target number
+    #[serde(default)]
+    pub verified: bool,
+    /// Flagged as an auto-generated phone directory page
+    #[serde(default)]
+    pub directory_spam: bool,
+    /// Why the result was flagged as directory spam
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spam_reason: Option<String>,
 }
 
 impl SearchResult {
@@ -68,8 +94,39 @@ impl SearchResult {
             title,
             snippet,
             source,
+            url: String::new(),
+            format: String::new(),
+            rank: 0,
+            template: String::new(),
+            match_mode: None,
+            verified: false,
+            directory_spam: false,
+            spam_reason: None,
         }
     }
+
+    /// Attach the result page URL
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Title, snippet and URL joined for matching
+    pub fn full_text(&self) -> String {
+        format!("{} {} {}", self.title, self.snippet, self.url)
+    }
+
+    /// Mark the result verified if its text or URL contains the target number
+    pub fn verify(&mut self, formatter: &PhoneFormatter) {
+        self.verified = formatter.matches_text(&self.full_text());
+    }
+
+    /// Lowercased host of the result URL, without a leading "www."
+    pub fn domain(&self) -> Option<String> {
+        let url = reqwest::Url::parse(&self.url).ok()?;
+        let host = url.host_str()?.to_lowercase();
+        Some(host.strip_prefix("www.").unwrap_or(&host).to_string())
+    }
 }
 
 #[allow(dead_code)]
@@ -104,3 +161,28 @@ pub fn create_client_with_timeout(timeout_secs: u64, random_ua: bool) -> reqwest
 pub fn create_client_from_config(config: &SearchConfig) -> reqwest::Client {
     create_client_with_timeout(config.timeout_secs, config.random_user_agent)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_with_address_or_age_before_number() {
+        let formatter = PhoneFormatter::new("2155551212").unwrap();
+        let verified = |title: &str, snippet: &str| {
+            let mut result = SearchResult::new(title.to_string(), snippet.to_string(), "Google".to_string());
+            result.verify(&formatter);
+            result.verified
+        };
+        assert!(verified("John Smith", "123 Main St, Philadelphia, PA 19102 (215) 555-1212"));
+        assert!(verified("John Smith, Age 45", "215.555.1212 - Landline"));
+        assert!(verified("Reverse lookup 19102", "215-555-1212 belongs to"));
+        assert!(verified("4521 Oak Ave", "Phone: 1 215 555 1212"));
+        assert!(!verified("John Smith", "123 Main St, Philadelphia, PA 19102 (215) 555-1213"));
+
+        let mut result = SearchResult::new(String::new(), String::new(), "Bing".to_string())
+            .with_url("https://directory.example/19102/215-555-1212");
+        result.verify(&formatter);
+        assert!(result.verified);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::search::SearchResult;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// Auto-generated "who called me" / number-range directory sites
+const DIRECTORY_DOMAINS: &[&str] = &[
+    "800notes.com",
+    "whocalledme.com",
+    "whocallsme.com",
+    "who-called.co",
+    "whocalled.us",
+    "callercenter.com",
+    "callercomplaints.com",
+    "shouldianswer.com",
+    "shouldianswer.net",
+    "unknownphone.com",
+    "tellows.com",
+    "numberguru.com",
+    "okcaller.com",
+    "revealname.com",
+    "reversephonecheck.com",
+    "findwhocallsyou.com",
+    "callerinfo.net",
+    "phonenumberlookup.net",
+    "whycall.me",
+    "calleridtest.com",
+    "areacodelookups.net",
+    "411.info",
+];
+
+/// Phrases typical of auto-generated directory pages
+const DIRECTORY_PHRASES: &[&str] = &[
+    "who called me",
+    "who called from",
+    "who is calling",
+    "number range",
+    "reverse phone lookup",
+    "phone numbers in the",
+    "numbers starting with",
+    "unknown caller",
+    "caller id lookup",
+];
+
+/// Phone numbers in one snippet at which it is treated as a listing
+const MAX_NUMBERS_PER_SNIPPET: usize = 3;
+
+lazy_static! {
+    static ref PHONE_LIKE_PATTERN: Regex =
+        Regex::new(r"\(?\b\d{3}\)?[-.\s]?\d{3}[-.\s]?\d{4}\b").unwrap();
+    static ref NUMBER_RANGE_PATTERN: Regex = Regex::new(
+        r"(?i)\b\d{3}[-.\s]?\d{3}[-.\s]?(?:x{4}|\*{4}|#{4})\b|\b\d{3}[-.\s]\d{3}[-.\s]\d{4}\s*(?:-|–|to|through)\s*\d{3}[-.\s]\d{3}[-.\s]\d{4}\b"
+    ).unwrap();
+}
+
+/// Classifies results from SEO phone-directory pages
+pub struct SpamClassifier {
+    domains: Vec<String>,
+}
+
+impl SpamClassifier {
+    /// Create a classifier using the built-in domain list plus extra domains
+    pub fn new(extra_domains: &[String]) -> Self {
+        let mut domains: Vec<String> = DIRECTORY_DOMAINS.iter().map(|d| d.to_string()).collect();
+        domains.extend(extra_domains.iter().map(|d| d.trim().to_lowercase()));
+        SpamClassifier { domains }
+    }
+
+    /// Return the reason a result looks like directory spam, if it does
+    pub fn classify(&self, result: &SearchResult) -> Option<String> {
+        if let Some(domain) = result.domain() {
+            if let Some(listed) = self
+                .domains
+                .iter()
+                .find(|d| domain == **d || domain.ends_with(&format!(".{}", d)))
+            {
+                return Some(format!("known directory domain ({})", listed));
+            }
+        }
+
+        let text = format!("{} {}", result.title, result.snippet);
+
+        if NUMBER_RANGE_PATTERN.is_match(&text) {
+            return Some("number range listing".to_string());
+        }
+
+        let numbers = PHONE_LIKE_PATTERN.find_iter(&result.snippet).count();
+        if numbers >= MAX_NUMBERS_PER_SNIPPET {
+            return Some(format!("{} phone numbers in snippet", numbers));
+        }
+
+        let lower = text.to_lowercase();
+        if numbers >= 2 {
+            if let Some(phrase) = DIRECTORY_PHRASES.iter().find(|p| lower.contains(*p)) {
+                return Some(format!("directory phrase \"{}\" with multiple numbers", phrase));
+            }
+        }
+
+        None
+    }
+
+    /// Flag every directory spam result in place, returning how many were flagged
+    pub fn flag_all(&self, results: &mut [SearchResult]) -> usize {
+        let mut flagged = 0;
+        for result in results.iter_mut() {
+            if let Some(reason) = self.classify(result) {
+                result.directory_spam = true;
+                result.spam_reason = Some(reason);
+                flagged += 1;
+            }
+        }
+        flagged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(snippet: &str, url: &str) -> SearchResult {
+        SearchResult::new("Title".to_string(), snippet.to_string(), "Google".to_string()).with_url(url)
+    }
+
+    #[test]
+    fn test_known_domain() {
+        let classifier = SpamClassifier::new(&[]);
+        let r = result("Comments about 555-555-1212", "https://www.800notes.com/Phone.aspx/1-555-555-1212");
+        assert!(classifier.classify(&r).unwrap().contains("800notes.com"));
+
+        let classifier = SpamClassifier::new(&["Spammy.example".to_string()]);
+        let r = result("Nothing special", "https://sub.spammy.example/555");
+        assert!(classifier.classify(&r).is_some());
+    }
+
+    #[test]
+    fn test_content_heuristics() {
+        let classifier = SpamClassifier::new(&[]);
+
+        let listing = result(
+            "555-555-1200 John Doe, 555-555-1201 Mary Major, 555-555-1202 Richard Roe",
+            "https://example.org/555-555",
+        );
+        assert!(classifier.classify(&listing).is_some());
+
+        let range = result("Numbers 555-555-XXXX in Springfield", "https://example.org/");
+        assert_eq!(classifier.classify(&range).as_deref(), Some("number range listing"));
+
+        let genuine = result("Call John Smith at 555-555-1212 for plumbing", "https://smithplumbing.example/");
+        assert!(classifier.classify(&genuine).is_none());
+    }
+}